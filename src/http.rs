@@ -1,30 +1,203 @@
-pub use failure::Error;
+use std::time::Duration;
+
+/// Error returned by an `HttpClient`.
+pub type Error = Box<dyn std::error::Error + Send + Sync>;
 
 pub trait HttpClient {
     fn user_agent(&mut self, user_agent: String);
     fn get<'a, I>(&self, base_url: &str, args: I) -> Result<String, Error>
     where
         I: Iterator<Item = (&'a str, &'a str)>;
+
+    /// Sends `args` in the body of a POST request, used when they are too
+    /// long to fit in a url. Clients that do not support it fall back to
+    /// `get`.
+    fn post<'a, I>(&self, base_url: &str, args: I) -> Result<String, Error>
+    where
+        I: Iterator<Item = (&'a str, &'a str)>,
+    {
+        self.get(base_url, args)
+    }
+
+    /// Downloads the raw contents of `url`, such as an image file. Clients
+    /// that do not support it fall back to `get`, which only works for
+    /// text.
+    fn get_bytes(&self, url: &str) -> Result<Vec<u8>, Error> {
+        self.get(url, std::iter::empty()).map(|x| x.into_bytes())
+    }
+
+    /// Waits for `duration` before retrying a request. Clients can replace
+    /// it, for example so tests do not actually wait.
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
 }
 
 #[cfg(feature = "http-client")]
 pub mod default {
-    use failure::err_msg;
     use reqwest;
     use std::io::Read;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
 
     use super::{Error, HttpClient};
 
+    /// `HttpClient` backed by reqwest. With the `compression` feature it
+    /// accepts gzip and brotli compressed responses, and with the `rustls`
+    /// feature it uses rustls instead of the system TLS library.
+    #[derive(Clone)]
     pub struct Client {
         user_agent: String,
+        /// Maximum time to wait for a request to complete.
+        timeout: Option<Duration>,
+        /// Number of times to retry a request that failed transiently.
+        max_retries: u32,
+        /// Delay before the first retry, doubled on every following one.
+        retry_delay: Duration,
+        /// Extra headers sent with every request.
+        headers: reqwest::header::HeaderMap,
+        /// Proxy to send requests through.
+        proxy: Option<reqwest::Proxy>,
+        /// Waits between retries, see `set_sleep`.
+        sleep: Arc<dyn Fn(Duration) + Send + Sync>,
+        /// Underlying reqwest client, shared across requests to reuse connections.
+        client: reqwest::blocking::Client,
     }
 
     impl Default for Client {
         fn default() -> Self {
             Client {
                 user_agent: "".to_owned(),
+                timeout: None,
+                max_retries: 0,
+                retry_delay: Duration::from_millis(500),
+                headers: reqwest::header::HeaderMap::new(),
+                proxy: None,
+                sleep: Arc::new(thread::sleep),
+                client: Client::builder().build().expect("failed to build the http client"),
+            }
+        }
+    }
+
+    impl Client {
+        /// Sets the maximum time to wait for a request to complete.
+        /// `None` disables the timeout.
+        pub fn set_timeout(&mut self, timeout: Option<Duration>) -> Result<(), Error> {
+            self.timeout = timeout;
+            self.rebuild()
+        }
+
+        /// Sends all the requests through `proxy`.
+        pub fn set_proxy(&mut self, proxy: reqwest::Proxy) -> Result<(), Error> {
+            self.proxy = Some(proxy);
+            self.rebuild()
+        }
+
+        /// Sets how many times a request is retried when the connection
+        /// fails, times out, or the server answers 429 or 5xx.
+        /// Defaults to 0, never retrying.
+        pub fn set_max_retries(&mut self, max_retries: u32) {
+            self.max_retries = max_retries;
+        }
+
+        /// Sets the delay before the first retry. Each following retry waits
+        /// twice as long as the previous one, unless the server sends a
+        /// `Retry-After` header.
+        pub fn set_retry_delay(&mut self, retry_delay: Duration) {
+            self.retry_delay = retry_delay;
+        }
+
+        /// Sets the function used to wait between retries, instead of
+        /// `std::thread::sleep`. Useful to test a retry configuration
+        /// without waiting.
+        pub fn set_sleep<F>(&mut self, sleep: F)
+        where
+            F: Fn(Duration) + Send + Sync + 'static,
+        {
+            self.sleep = Arc::new(sleep);
+        }
+
+        /// Sets a header to send with every request, such as
+        /// `Api-User-Agent`, replacing any previous value for `name`.
+        pub fn set_header(&mut self, name: &str, value: &str) -> Result<(), Error> {
+            let name = reqwest::header::HeaderName::from_bytes(name.as_bytes())?;
+            let value = reqwest::header::HeaderValue::from_str(value)?;
+            self.headers.insert(name, value);
+            Ok(())
+        }
+
+        /// Returns the delay requested by the server in a `Retry-After`
+        /// header, if it is a number of seconds.
+        fn retry_after(response: &reqwest::blocking::Response) -> Option<Duration> {
+            response
+                .headers()
+                .get(reqwest::header::RETRY_AFTER)
+                .and_then(|x| x.to_str().ok())
+                .and_then(|x| x.trim().parse().ok())
+                .map(Duration::from_secs)
+        }
+
+        /// Sends the request built by `request`, retrying it as configured
+        /// with `set_max_retries`, and returns the successful response.
+        fn send<F>(&self, request: F) -> Result<reqwest::blocking::Response, Error>
+        where
+            F: Fn() -> reqwest::blocking::RequestBuilder,
+        {
+            let mut attempt = 0;
+            loop {
+                let result = request()
+                    .headers(self.headers.clone())
+                    .header(reqwest::header::USER_AGENT, self.user_agent.clone())
+                    .send();
+                let can_retry = attempt < self.max_retries;
+                let retry_after = match result {
+                    Ok(response) => {
+                        let status = response.status();
+                        if can_retry && (status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error()) {
+                            Client::retry_after(&response)
+                        } else {
+                            return Ok(response.error_for_status()?);
+                        }
+                    },
+                    Err(e) => {
+                        if can_retry && (e.is_connect() || e.is_timeout()) {
+                            None
+                        } else {
+                            return Err(e.into());
+                        }
+                    },
+                };
+                self.sleep(retry_after.unwrap_or(self.retry_delay.saturating_mul(2u32.saturating_pow(attempt))));
+                attempt += 1;
             }
         }
+
+        /// Reads the body of `response` as text.
+        fn read_text(mut response: reqwest::blocking::Response) -> Result<String, Error> {
+            let mut response_str = String::new();
+            response.read_to_string(&mut response_str)?;
+            Ok(response_str)
+        }
+
+        /// Returns a reqwest client builder using the TLS backend selected
+        /// by the cargo features.
+        fn builder() -> reqwest::blocking::ClientBuilder {
+            let builder = reqwest::blocking::Client::builder();
+            #[cfg(feature = "rustls")]
+            let builder = builder.use_rustls_tls();
+            builder
+        }
+
+        /// Rebuilds the underlying reqwest client with the current settings.
+        fn rebuild(&mut self) -> Result<(), Error> {
+            let mut builder = Client::builder().timeout(self.timeout);
+            if let Some(ref proxy) = self.proxy {
+                builder = builder.proxy(proxy.clone());
+            }
+            self.client = builder.build()?;
+            Ok(())
+        }
     }
 
     impl HttpClient for Client {
@@ -37,17 +210,28 @@ pub mod default {
             I: Iterator<Item = (&'a str, &'a str)>,
         {
             let url = reqwest::Url::parse_with_params(base_url, args)?;
-            let client = reqwest::blocking::Client::new();
-            let mut response = client
-                .get(url)
-                .header(reqwest::header::USER_AGENT, self.user_agent.clone())
-                .send()?;
+            Client::read_text(self.send(|| self.client.get(url.clone()))?)
+        }
+
+        fn post<'a, I>(&self, base_url: &str, args: I) -> Result<String, Error>
+        where
+            I: Iterator<Item = (&'a str, &'a str)>,
+        {
+            let url = reqwest::Url::parse(base_url)?;
+            let form: Vec<_> = args.collect();
+            Client::read_text(self.send(|| self.client.post(url.clone()).form(&form))?)
+        }
 
-            ensure!(response.status().is_success(), err_msg("Bad status"));
+        fn get_bytes(&self, url: &str) -> Result<Vec<u8>, Error> {
+            let url = reqwest::Url::parse(url)?;
+            let mut response = self.send(|| self.client.get(url.clone()))?;
+            let mut bytes = Vec::new();
+            response.read_to_end(&mut bytes)?;
+            Ok(bytes)
+        }
 
-            let mut response_str = String::new();
-            response.read_to_string(&mut response_str)?;
-            Ok(response_str)
+        fn sleep(&self, duration: Duration) {
+            (self.sleep)(duration)
         }
     }
 }
@@ -3,55 +3,159 @@ use std::marker::PhantomData;
 
 use serde_json::Value;
 
-use super::{Page, Result, http};
+use super::{Error, Page, Result, http};
 
-pub struct Iter<'a, A: 'a + http::HttpClient, B: IterItem> {
-    page: &'a Page<'a, A>,
+/// The parameters to send to get the next batch of results, or `None` if
+/// there are no more.
+pub type Continuation = Option<Vec<(String, String)>>;
+
+/// A batch of results and the continuation to request the next one.
+pub type Batch = (Vec<Value>, Continuation);
+
+/// The results of consecutive requests, fetching the next batch when the
+/// current one runs out.
+struct Batches {
     inner: IntoIter<Value>,
-    cont: Option<Vec<(String, String)>>,
+    cont: Continuation,
+}
+
+impl Batches {
+    fn new((array, cont): Batch) -> Batches {
+        Batches {
+            inner: array.into_iter(),
+            cont,
+        }
+    }
+
+    /// Returns the next result, calling `request` with the continuation
+    /// when the current batch is exhausted. A failed request is returned,
+    /// and retried on the following call.
+    fn next<F: FnMut(&Continuation) -> Result<Batch>>(&mut self, mut request: F) -> Option<Result<Value>> {
+        loop {
+            if let Some(v) = self.inner.next() {
+                return Some(Ok(v));
+            }
+            self.cont.as_ref()?;
+            match request(&self.cont) {
+                Ok((array, cont)) => {
+                    self.inner = array.into_iter();
+                    self.cont = cont;
+                },
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Iterates over the results of a `Page` query, requesting more as needed.
+/// Results the api returns in an unexpected shape are skipped, and the
+/// iteration ends at the first request that fails; use `try_iter` to see
+/// those errors.
+pub struct Iter<'a, A: 'a + http::HttpClient, B: IterItem> {
+    page: Page<'a, A>,
+    batches: Batches,
     phantom: PhantomData<B>
 }
 
 impl<'a, A: http::HttpClient, B: IterItem> Iter<'a, A, B> {
-    pub fn new(page: &'a Page<A>) -> Result<Iter<'a, A, B>> {
-        let (array, cont) = B::request_next(page, &None)?;
+    pub fn new(page: &Page<'a, A>) -> Result<Iter<'a, A, B>> {
         Ok(Iter {
-            page: page,
-            inner: array.into_iter(),
-            cont: cont,
+            page: page.clone(),
+            batches: Batches::new(B::request_next(page, &None)?),
             phantom: PhantomData,
         })
     }
 
-    fn fetch_next(&mut self) -> Result <()> {
-        if self.cont.is_some() {
-            let (array, cont) = B::request_next(self.page, &self.cont)?;
-            self.inner = array.into_iter();
-            self.cont = cont;
+    /// Creates an iterator starting at the results pointed by `cont`, as
+    /// returned by `continuation`.
+    pub fn resume_from(page: &Page<'a, A>, cont: Vec<(String, String)>) -> Result<Iter<'a, A, B>> {
+        Ok(Iter {
+            page: page.clone(),
+            batches: Batches::new(B::request_next(page, &Some(cont))?),
+            phantom: PhantomData,
+        })
+    }
+
+    /// Returns the token to request the results following the ones already
+    /// fetched, or `None` if there are no more. Results fetched but not yet
+    /// returned by the iterator are not included when resuming with it.
+    pub fn continuation(&self) -> Continuation {
+        self.batches.cont.clone()
+    }
+
+    /// Converts this iterator into one that reports errors fetching the
+    /// following pages instead of silently ending.
+    pub fn try_iter(self) -> TryIter<'a, A, B> {
+        TryIter { iter: self }
+    }
+
+    /// The next item for `TryIter`, skipping results in an unexpected shape.
+    fn try_next(&mut self) -> Option<Result<B>> {
+        let page = &self.page;
+        loop {
+            match self.batches.next(|cont| B::request_next(page, cont))? {
+                Ok(v) => if let Some(b) = B::from_value(&v) {
+                    return Some(Ok(b));
+                },
+                Err(e) => return Some(Err(e)),
+            }
         }
-        Ok(())
     }
 }
 
 impl<'a, A: http::HttpClient, B: IterItem> Iterator for Iter<'a, A, B> {
     type Item = B;
     fn next(&mut self) -> Option<Self::Item> {
-        match self.inner.next() {
-            Some(ref v) => B::from_value(&v),
-            None => match self.cont {
-                Some(_) => match self.fetch_next() {
-                    Ok(_) => self.inner.next().and_then(|x| B::from_value(&x)),
-                    Err(_) => None,
-                },
-                None => None,
-            }
-        }
+        self.try_next()?.ok()
+    }
+}
+
+/// Like `Iter`, but yields `Err` when fetching a page fails. After an error
+/// the iterator can be polled again to retry the same request. Results in
+/// an unexpected shape are skipped, as in `Iter`.
+pub struct TryIter<'a, A: 'a + http::HttpClient, B: IterItem> {
+    iter: Iter<'a, A, B>,
+}
+
+impl<'a, A: http::HttpClient, B: IterItem> Iterator for TryIter<'a, A, B> {
+    type Item = Result<B>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.try_next()
+    }
+}
+
+/// Iterates over the raw results of a `prop` module.
+/// See `Page::query_prop_iter`.
+pub struct PropIter<'a, A: 'a + http::HttpClient> {
+    page: Page<'a, A>,
+    prop: String,
+    limit_param: String,
+    array_key: String,
+    batches: Batches,
+}
+
+impl<'a, A: http::HttpClient> PropIter<'a, A> {
+    pub fn new(page: &Page<'a, A>, prop: &str, limit_param: &str, array_key: &str) -> Result<PropIter<'a, A>> {
+        Ok(PropIter {
+            page: page.clone(),
+            prop: prop.to_owned(),
+            limit_param: limit_param.to_owned(),
+            array_key: array_key.to_owned(),
+            batches: Batches::new(page.request_prop(&None, prop, limit_param, array_key)?),
+        })
+    }
+}
+
+impl<'a, A: http::HttpClient> Iterator for PropIter<'a, A> {
+    type Item = Value;
+    fn next(&mut self) -> Option<Self::Item> {
+        let (page, prop, limit_param, array_key) = (&self.page, &self.prop, &self.limit_param, &self.array_key);
+        self.batches.next(|cont| page.request_prop(cont, prop, limit_param, array_key))?.ok()
     }
 }
 
 pub trait IterItem: Sized {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)>;
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch>;
     fn from_value(value: &Value) -> Option<Self>;
 }
 
@@ -60,11 +164,46 @@ pub struct Image {
     pub url: String,
     pub title: String,
     pub description_url: String,
+    /// Width in pixels, if known
+    pub width: Option<u32>,
+    /// Height in pixels, if known
+    pub height: Option<u32>,
+    /// File size in bytes, if known
+    pub size: Option<u32>,
+    /// MIME type, for example "image/jpeg"
+    pub mime: Option<String>,
+}
+
+impl Image {
+    /// Downloads the image file using `client`, usually the one of the
+    /// `Wikipedia` the image was found with.
+    pub fn download<A: http::HttpClient>(&self, client: &A) -> Result<Vec<u8>> {
+        client.get_bytes(&self.url).map_err(Error::HTTPError)
+    }
+
+    /// Builds the url of a thumbnail of the image `width` pixels wide,
+    /// following the scheme used by Wikimedia's file servers. Vector
+    /// images get a PNG thumbnail. The url is returned unchanged if it
+    /// does not look like an original file in one of those servers.
+    pub fn thumbnail_url(&self, width: u32) -> String {
+        let mut parts: Vec<&str> = self.url.rsplitn(4, '/').collect();
+        let valid = parts.len() == 4
+            && parts[3].starts_with("https://upload.wikimedia.org/")
+            && !parts[3].ends_with("/thumb")
+            && parts[2].len() == 1
+            && parts[1].len() == 2;
+        if !valid {
+            return self.url.clone();
+        }
+        parts.reverse();
+        let name = parts[3];
+        let suffix = if name.to_lowercase().ends_with(".svg") { ".png" } else { "" };
+        format!("{}/thumb/{}/{}/{}/{}px-{}{}", parts[0], parts[1], parts[2], name, width, name, suffix)
+    }
 }
 
 impl IterItem for Image {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)> {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
         page.request_images(&cont)
     }
 
@@ -78,27 +217,35 @@ impl IterItem for Image {
             .get("title")
             .and_then(|x| x.as_str())
             .unwrap_or("").to_owned();
-        let url = obj
+        let info = obj
             .get("imageinfo")
             .and_then(|x| x.as_array())
-            .and_then(|x| x.into_iter().next())
-            .and_then(|x| x.as_object())
+            .and_then(|x| x.iter().next())
+            .and_then(|x| x.as_object());
+        let url = info
             .and_then(|x| x.get("url"))
             .and_then(|x| x.as_str())
             .unwrap_or("").to_owned();
-        let description_url = obj
-            .get("imageinfo")
-            .and_then(|x| x.as_array())
-            .and_then(|x| x.into_iter().next())
-            .and_then(|x| x.as_object())
+        let description_url = info
             .and_then(|x| x.get("descriptionurl"))
             .and_then(|x| x.as_str())
             .unwrap_or("").to_owned();
+        let number = |key| info
+            .and_then(|x| x.get(key))
+            .and_then(|x| x.as_u64())
+            .map(|x| x as u32);
 
         Some(Image {
             url: url.to_owned(),
             title: title.to_owned(),
             description_url: description_url.to_owned(),
+            width: number("width"),
+            height: number("height"),
+            size: number("size"),
+            mime: info
+                .and_then(|x| x.get("mime"))
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_owned()),
         })
     }
 }
@@ -106,11 +253,15 @@ impl IterItem for Image {
 #[derive(Debug, PartialEq)]
 pub struct Reference {
     pub url: String,
+    /// The scheme of `url`, for example "https" or "ftp"
+    pub scheme: String,
+    /// True if the link had no scheme in the article, in which case
+    /// "https" is used
+    pub protocol_relative: bool,
 }
 
 impl IterItem for Reference {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)> {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
         page.request_extlinks(&cont)
     }
 
@@ -119,12 +270,18 @@ impl IterItem for Reference {
             .as_object()
             .and_then(|x| x.get("*"))
             .and_then(|x| x.as_str())
-            .map(|s| Reference {
-                url: if s.starts_with("http:") {
-                    s.to_owned()
+            .map(|s| {
+                let protocol_relative = s.starts_with("//");
+                let url = if protocol_relative {
+                    format!("https:{}", s)
                 } else {
-                    format!("http:{}", s)
-                },
+                    s.to_owned()
+                };
+                Reference {
+                    scheme: url.split(':').next().unwrap_or("").to_lowercase(),
+                    url,
+                    protocol_relative,
+                }
             })
     }
 }
@@ -135,8 +292,7 @@ pub struct Link {
 }
 
 impl IterItem for Link {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)> {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
         page.request_links(&cont)
     }
 
@@ -156,46 +312,218 @@ pub struct LangLink {
 
     /// The page title in this language, may be `None` if undefined
     pub title: Option<String>,
+
+    /// The full url of the page in this language
+    pub url: Option<String>,
 }
 
 impl IterItem for LangLink {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)> {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
         page.request_langlinks(&cont)
     }
 
     fn from_value(value: &Value) -> Option<LangLink> {
-        value
-            .as_object()
-            .map(|l| LangLink {
-                lang: l.get("lang").unwrap().as_str().unwrap().into(),
-                title: l.get("*").and_then(|n| n.as_str()).map(|n| n.into()),
-            })
+        let l = value.as_object()?;
+        Some(LangLink {
+            lang: l.get("lang").and_then(|n| n.as_str())?.into(),
+            title: l.get("*").and_then(|n| n.as_str()).map(|n| n.into()),
+            url: l.get("url").and_then(|n| n.as_str()).map(|n| n.into()),
+        })
     }
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Category {
+    /// Title of the category without the namespace prefix, for example
+    /// "Programming languages"
     pub title: String,
+    /// Only filled by `Page::get_categories_with_ids`, and `None` if the
+    /// category page does not exist
+    pub pageid: Option<u64>,
+    /// The namespace, 14 for categories
+    pub ns: i32,
+    /// True for hidden categories, usually used for maintenance
+    pub hidden: bool,
+    /// Hexadecimal key used to sort the page within the category
+    pub sortkey: Option<String>,
+    /// Human readable part of the sort key, empty if the default one is used
+    pub sortkeyprefix: Option<String>,
 }
 
 impl IterItem for Category {
-    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Option<Vec<(String, String)>>)
-            -> Result<(Vec<Value>, Option<Vec<(String, String)>>)> {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
         page.request_categories(&cont)
     }
 
     fn from_value(value: &Value) -> Option<Category> {
+        let obj = value.as_object()?;
+        obj.get("title")
+            .and_then(|x| x.as_str())
+            .map(|s| Category {
+                title: s.to_owned(),
+                pageid: obj.get("pageid").and_then(|x| x.as_u64()),
+                ns: obj.get("ns").and_then(|x| x.as_i64()).map(|x| x as i32).unwrap_or(super::CATEGORY_NAMESPACE),
+                hidden: obj.contains_key("hidden"),
+                sortkey: obj.get("sortkey").and_then(|x| x.as_str()).map(|x| x.to_owned()),
+                sortkeyprefix: obj.get("sortkeyprefix").and_then(|x| x.as_str()).map(|x| x.to_owned()),
+            })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Template {
+    pub title: String,
+}
+
+impl IterItem for Template {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_templates(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<Template> {
         value
             .as_object()
             .and_then(|x| x.get("title"))
             .and_then(|x| x.as_str())
-            .map(|s| Category {
-                title: if s.starts_with("Category: ") {
-                    s[10..].to_owned()
-                } else {
-                    s.to_owned()
-                },
-            })
+            .map(|s| Template { title: s.to_owned() })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CategoryMember {
+    pub title: String,
+    pub pageid: u64,
+    /// The namespace of the member, for example 0 for articles and 14 for
+    /// subcategories
+    pub ns: i32,
+}
+
+impl IterItem for CategoryMember {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_category_members(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<CategoryMember> {
+        let obj = value.as_object()?;
+        Some(CategoryMember {
+            title: obj.get("title").and_then(|x| x.as_str())?.to_owned(),
+            pageid: obj.get("pageid").and_then(|x| x.as_u64())?,
+            ns: obj.get("ns").and_then(|x| x.as_i64())? as i32,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Backlink {
+    pub title: String,
+    pub pageid: u64,
+}
+
+impl IterItem for Backlink {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_backlinks(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<Backlink> {
+        let obj = value.as_object()?;
+        Some(Backlink {
+            title: obj.get("title").and_then(|x| x.as_str())?.to_owned(),
+            pageid: obj.get("pageid").and_then(|x| x.as_u64())?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Revision {
+    pub revid: u64,
+    /// The previous revision, 0 for the page creation
+    pub parentid: u64,
+    pub timestamp: String,
+    /// The author, `None` if hidden
+    pub user: Option<String>,
+    /// The edit summary, `None` if hidden
+    pub comment: Option<String>,
+    /// Size of the page in bytes after this revision
+    pub size: u64,
+}
+
+impl IterItem for Revision {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_revisions(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<Revision> {
+        let obj = value.as_object()?;
+        let string = |key| obj.get(key).and_then(|x| x.as_str()).map(|x| x.to_owned());
+        Some(Revision {
+            revid: obj.get("revid").and_then(|x| x.as_u64())?,
+            parentid: obj.get("parentid").and_then(|x| x.as_u64())?,
+            timestamp: string("timestamp")?,
+            user: string("user"),
+            comment: string("comment"),
+            size: obj.get("size").and_then(|x| x.as_u64())?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Contributor {
+    pub name: String,
+    pub userid: u64,
+}
+
+impl IterItem for Contributor {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_contributors(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<Contributor> {
+        let obj = value.as_object()?;
+        Some(Contributor {
+            name: obj.get("name").and_then(|x| x.as_str())?.to_owned(),
+            userid: obj.get("userid").and_then(|x| x.as_u64())?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Redirect {
+    pub title: String,
+    pub pageid: u64,
+}
+
+impl IterItem for Redirect {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_redirects(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<Redirect> {
+        let obj = value.as_object()?;
+        Some(Redirect {
+            title: obj.get("title").and_then(|x| x.as_str())?.to_owned(),
+            pageid: obj.get("pageid").and_then(|x| x.as_u64())?,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct IwLink {
+    /// The interwiki prefix of the other wiki, for example "wikt" for
+    /// Wiktionary
+    pub prefix: String,
+    pub title: String,
+}
+
+impl IterItem for IwLink {
+    fn request_next<A: http::HttpClient>(page: &Page<A>, cont: &Continuation) -> Result<Batch> {
+        page.request_iwlinks(cont)
+    }
+
+    fn from_value(value: &Value) -> Option<IwLink> {
+        let obj = value.as_object()?;
+        Some(IwLink {
+            prefix: obj.get("prefix").and_then(|x| x.as_str())?.to_owned(),
+            title: obj.get("*").and_then(|x| x.as_str())?.to_owned(),
+        })
     }
 }
@@ -12,17 +12,43 @@
 #[cfg(feature="http-client")] extern crate reqwest;
 #[cfg(feature="http-client")] extern crate url;
 extern crate serde_json;
-#[macro_use] extern crate failure;
+extern crate thiserror;
 
 use std::cmp::PartialEq;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io;
+use std::ops::Deref;
 use std::result;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 pub mod iter;
 pub mod http;
-pub use iter::Iter;
+pub use iter::{Iter, TryIter};
+use iter::{Batch, Continuation};
 
 const LANGUAGE_URL_MARKER:&'static str = "{language}";
+/// Number of times a request rejected because of `maxlag` is retried.
+const MAXLAG_RETRIES: u32 = 3;
+/// Seconds to wait before retrying a request rejected because of `maxlag`,
+/// when the api does not say how lagged it is.
+const MAXLAG_DELAY: u64 = 5;
+/// Requests whose parameters are longer than this are sent with POST.
+const MAX_GET_LENGTH: usize = 2000;
+/// Length of the longest chain of redirects that is followed.
+const MAX_REDIRECTS: usize = 10;
+/// Largest `rnlimit` accepted by the api for a single request.
+const MAX_RANDOM_RESULTS: u32 = 500;
+/// Width in pixels of the thumbnail returned by `Page::get_preview`.
+const PREVIEW_THUMBNAIL_SIZE: u32 = 320;
+/// Largest number of intro extracts the api returns in a single request.
+const MAX_INTRO_EXTRACTS: usize = 20;
+/// Id of the namespace of categories.
+const CATEGORY_NAMESPACE: i32 = 14;
+/// Largest number of titles the api accepts in a single request.
+const MAX_TITLES: usize = 50;
 
 macro_rules! results {
     ($data: expr, $query_field: expr) => {
@@ -56,7 +82,7 @@ macro_rules! cont {
             },
             None => params.push(("continue", "")),
         }
-        let q = $this.wikipedia.query(params.into_iter())?;
+        let q = $this.query(params.into_iter())?;
 
         let pages = q
             .as_object()
@@ -71,23 +97,55 @@ macro_rules! cont {
 }
 
 /// Wikipedia failed to fetch some information
-#[derive(Fail, Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum Error {
-    /// Some error communicating with the server
-    #[fail(display = "HTTP Error")]
-    HTTPError,
+    /// Some error communicating with the server. With the default client
+    /// the cause is a `reqwest::Error`, which holds the status code if any.
+    #[error("HTTP Error: {0}")]
+    HTTPError(#[source] http::Error),
     /// Error reading response
-    #[fail(display = "IO Error: {}", _0)]
-    IOError(#[cause] io::Error),
+    #[error("IO Error: {0}")]
+    IOError(#[source] io::Error),
     /// Failed to parse JSON response
-    #[fail(display = "JSON Error: {}", _0)]
-    JSONError(#[cause] serde_json::error::Error),
+    #[error("JSON Error: {0}")]
+    JSONError(#[source] serde_json::error::Error),
     /// Missing required keys in the JSON response
-    #[fail(display = "JSON Path Error")]
+    #[error("JSON Path Error")]
     JSONPathError,
     /// One of the parameters provided (identified by `String`) is invalid
-    #[fail(display = "Invalid Parameter: {}", _0)]
+    #[error("Invalid Parameter: {0}")]
     InvalidParameter(String),
+    /// The server returned an error, identified by `code` and described by
+    /// `info`
+    #[error("API Error: {code}: {info}")]
+    ApiError { code: String, info: String },
+    /// The redirects followed from a page loop back or are too many. The
+    /// `String` is the last title reached.
+    #[error("Redirect Loop: {0}")]
+    RedirectLoop(String),
+    /// The requested title cannot exist, for example because it has
+    /// forbidden characters. The `String` is the reason given by the server.
+    #[error("Invalid Title: {0}")]
+    InvalidTitle(String),
+}
+
+/// Errors are equal if they are the same variant with the same values.
+/// Errors with a cause are compared by their message, or by their kind for
+/// `IOError`.
+impl PartialEq for Error {
+    fn eq(&self, other: &Error) -> bool {
+        match (self, other) {
+            (Error::HTTPError(e1), Error::HTTPError(e2)) => e1.to_string() == e2.to_string(),
+            (Error::IOError(e1), Error::IOError(e2)) => e1.kind() == e2.kind(),
+            (Error::JSONError(e1), Error::JSONError(e2)) => e1.to_string() == e2.to_string(),
+            (Error::JSONPathError, Error::JSONPathError) => true,
+            (Error::InvalidParameter(p1), Error::InvalidParameter(p2)) => p1 == p2,
+            (Error::ApiError { code: c1, info: i1 }, Error::ApiError { code: c2, info: i2 }) => c1 == c2 && i1 == i2,
+            (Error::RedirectLoop(t1), Error::RedirectLoop(t2)) => t1 == t2,
+            (Error::InvalidTitle(r1), Error::InvalidTitle(r2)) => r1 == r2,
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -106,10 +164,37 @@ pub struct Wikipedia<A: http::HttpClient> {
     /// The iterator will go through all of them, fetching pages of this size.
     /// It can be the string "max" to fetch as many as possible on every request.
     pub images_results: String,
-    /// Like `images_results`, for links and references.
+    /// Like `images_results`, for links, backlinks, references, langlinks,
+    /// iwlinks, templates, revisions, contributors and redirects.
     pub links_results: String,
     /// Like `images_results`, for categories.
     pub categories_results: String,
+    /// If set, requests are sent with this `maxlag` value, asking the server
+    /// to reject them while its replication lag is higher than this number
+    /// of seconds. Rejected requests are retried after a pause.
+    pub maxlag: Option<u32>,
+    /// If set, requests are sent with this `assert` value, such as "user"
+    /// or "bot", so they fail with an `ApiError` instead of running
+    /// anonymously when the session is not authenticated.
+    pub assert: Option<String>,
+    /// If set, requests are sent with this `variant`, such as "zh-hans" or
+    /// "sr-el", so content and titles come back in that script on wikis
+    /// written in several of them.
+    pub variant: Option<String>,
+    /// If true, the content of revisions is requested without `rvslots`,
+    /// which servers older than MediaWiki 1.32 do not support.
+    pub legacy_revisions: bool,
+    /// Url of the rest api, used by `Page::get_rest_summary`. The substring
+    /// `{language}` is replaced with the selected language. If not set, it
+    /// is `/api/rest_v1` on the host of the action api.
+    pub rest_url: Option<String>,
+    /// Result of the last `get_languages` call, reused by the following ones.
+    languages: Mutex<Option<Vec<(String, String)>>>,
+    /// Warnings of the last response, see `last_warnings`.
+    warnings: Mutex<Vec<(String, String)>>,
+    /// Result of the last `get_namespaces` call, with the api url it was
+    /// requested from.
+    namespaces: Mutex<Option<(String, HashMap<i32, String>)>>,
 }
 
 impl<A: http::HttpClient + Default> Default for Wikipedia<A> {
@@ -129,6 +214,14 @@ impl<A: http::HttpClient + Clone> Clone for Wikipedia<A> {
             images_results: self.images_results.clone(),
             links_results: self.links_results.clone(),
             categories_results: self.categories_results.clone(),
+            maxlag: self.maxlag,
+            assert: self.assert.clone(),
+            variant: self.variant.clone(),
+            legacy_revisions: self.legacy_revisions,
+            rest_url: self.rest_url.clone(),
+            languages: Mutex::new(self.languages.lock().unwrap().clone()),
+            warnings: Mutex::new(Vec::new()),
+            namespaces: Mutex::new(self.namespaces.lock().unwrap().clone()),
         }
     }
 }
@@ -146,12 +239,123 @@ impl<A: http::HttpClient> Wikipedia<A> {
             images_results: "max".to_owned(),
             links_results: "max".to_owned(),
             categories_results: "max".to_owned(),
+            maxlag: None,
+            assert: None,
+            variant: None,
+            legacy_revisions: false,
+            rest_url: None,
+            languages: Mutex::new(None),
+            warnings: Mutex::new(Vec::new()),
+            namespaces: Mutex::new(None),
+        }
+    }
+
+    /// Sets the `language` and returns `self`, to chain configuration calls.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate wikipedia;
+    ///
+    /// let wiki = wikipedia::Wikipedia::<wikipedia::http::default::Client>::default()
+    ///     .with_language("es")
+    ///     .with_search_results(25);
+    /// assert_eq!(wiki.base_url(), "https://es.wikipedia.org/w/api.php");
+    /// ```
+    pub fn with_language(mut self, language: &str) -> Self {
+        self.language = language.to_owned();
+        self
+    }
+
+    /// Sets `search_results` and returns `self`.
+    pub fn with_search_results(mut self, search_results: u32) -> Self {
+        self.search_results = search_results;
+        self
+    }
+
+    /// Sets `images_results` and returns `self`.
+    pub fn with_images_results(mut self, images_results: &str) -> Self {
+        self.images_results = images_results.to_owned();
+        self
+    }
+
+    /// Sets `links_results` and returns `self`.
+    pub fn with_links_results(mut self, links_results: &str) -> Self {
+        self.links_results = links_results.to_owned();
+        self
+    }
+
+    /// Sets `categories_results` and returns `self`.
+    pub fn with_categories_results(mut self, categories_results: &str) -> Self {
+        self.categories_results = categories_results.to_owned();
+        self
+    }
+
+    /// Calls `set_base_url` and returns `self`.
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.set_base_url(base_url);
+        self
+    }
+
+    /// Creates a `Wikipedia` for any MediaWiki site given the url of its
+    /// api, for example "https://wiki.example.com/w/api.php".
+    pub fn for_site(api_url: &str) -> Self
+    where
+        A: Default,
+    {
+        Wikipedia::default().with_base_url(api_url)
+    }
+
+    /// Returns the names of the namespaces of the site, by id, for example
+    /// 14 is "Category" in English and "Categoría" in Spanish. They are
+    /// only requested once for each api url.
+    pub fn get_namespaces(&self) -> Result<HashMap<i32, String>> {
+        let base_url = self.base_url();
+        if let Some((ref url, ref namespaces)) = *self.namespaces.lock().unwrap() {
+            if *url == base_url {
+                return Ok(namespaces.clone());
+            }
         }
+        let q = self.query(vec![
+            ("meta", "siteinfo"),
+            ("siprop", "namespaces"),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        let namespaces: HashMap<_, _> = q
+            .as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("namespaces"))
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?
+            .values()
+            .filter_map(|x| {
+                let x = x.as_object()?;
+                Some((
+                    x.get("id")?.as_i64()? as i32,
+                    x.get("*")?.as_str()?.to_owned(),
+                ))
+            })
+            .collect();
+        *self.namespaces.lock().unwrap() = Some((base_url, namespaces.clone()));
+        Ok(namespaces)
     }
 
     /// Returns a list of languages in the form of (`identifier`, `language`),
     /// for example [("en", "English"), ("es", "Español")]
+    /// The list is only requested once, see `refresh_languages`.
     pub fn get_languages(&self) -> Result<Vec<(String, String)>> {
+        if let Some(ref languages) = *self.languages.lock().unwrap() {
+            return Ok(languages.clone());
+        }
+        self.refresh_languages()
+    }
+
+    /// Like `get_languages`, but always requests the list, updating the
+    /// one returned by the following `get_languages` calls.
+    pub fn refresh_languages(&self) -> Result<Vec<(String, String)>> {
         let q = self.query(vec![
             ("meta", "siteinfo"),
             ("siprop", "languages"),
@@ -159,7 +363,7 @@ impl<A: http::HttpClient> Wikipedia<A> {
             ("action", "query"),
         ].into_iter())?;
 
-        Ok(q
+        let languages: Vec<_> = q
             .as_object()
             .and_then(|x| x.get("query"))
             .and_then(|x| x.as_object())
@@ -186,7 +390,9 @@ impl<A: http::HttpClient> Wikipedia<A> {
                                 },
                         ))
                     })
-            .collect())
+            .collect();
+        *self.languages.lock().unwrap() = Some(languages.clone());
+        Ok(languages)
     }
 
     /// Returns the api url
@@ -194,9 +400,28 @@ impl<A: http::HttpClient> Wikipedia<A> {
         format!("{}{}{}", self.pre_language_url, self.language, self.post_language_url)
     }
 
+    /// Returns the url of the rest api, without a trailing slash.
+    pub fn rest_base_url(&self) -> String {
+        match self.rest_url {
+            Some(ref url) => url.replace(LANGUAGE_URL_MARKER, &self.language).trim_end_matches('/').to_owned(),
+            None => format!("https://{}/api/rest_v1", self.host()),
+        }
+    }
+
+    /// Sets the `language`, checking first that it is one of the codes
+    /// returned by `get_languages`.
+    pub fn set_language(&mut self, language: &str) -> Result<()> {
+        if !self.get_languages()?.iter().any(|(code, _)| code == language) {
+            return Err(Error::InvalidParameter("language".to_string()));
+        }
+        self.language = language.to_owned();
+        Ok(())
+    }
+
     /// Updates the url format. The substring `{language}` will be replaced
     /// with the selected language.
     pub fn set_base_url(&mut self, base_url: &str) {
+        *self.languages.lock().unwrap() = None;
         let index = match base_url.find(LANGUAGE_URL_MARKER) {
             Some(i) => i,
             None => {
@@ -210,13 +435,104 @@ impl<A: http::HttpClient> Wikipedia<A> {
         self.post_language_url = base_url[index+LANGUAGE_URL_MARKER.len()..].to_owned();
     }
 
+    /// Sends a request to the api with `args`, for modules this crate does
+    /// not wrap, and returns the parsed response. `format` is json unless
+    /// given, and `maxlag`, `assert` and `variant` are added as configured.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate wikipedia;
+    ///
+    /// let wiki = wikipedia::Wikipedia::<wikipedia::http::default::Client>::default();
+    /// let response = wiki.query_raw(&[
+    ///     ("action", "query"),
+    ///     ("meta", "siteinfo"),
+    ///     ("siprop", "statistics"),
+    /// ]).unwrap();
+    /// assert!(response["query"]["statistics"]["articles"].as_u64().unwrap() > 0);
+    /// ```
+    pub fn query_raw(&self, args: &[(&str, &str)]) -> Result<serde_json::Value> {
+        let mut args = args.to_vec();
+        if !args.iter().any(|&(k, _)| k == "format") {
+            args.push(("format", "json"));
+        }
+        self.query(args.into_iter())
+    }
+
     fn query<'a, I>(&self, args: I) -> Result<serde_json::Value>
             where I: Iterator<Item=(&'a str, &'a str)> {
-        let response_str = self.client.get(&*self.base_url(), args).map_err(|_| Error::HTTPError)?;
-        let json = serde_json::from_str(&*response_str).map_err(Error::JSONError)?;
+        let maxlag = self.maxlag.map(|x| x.to_string());
+        let mut args: Vec<_> = args.collect();
+        if let Some(ref m) = maxlag {
+            args.push(("maxlag", m));
+        }
+        if let Some(ref a) = self.assert {
+            args.push(("assert", a));
+        }
+        if let Some(ref v) = self.variant {
+            args.push(("variant", v));
+            if args.contains(&("action", "query")) {
+                args.push(("converttitles", ""));
+            }
+        }
+        let mut attempt = 0;
+        loop {
+            let json = self.query_once(args.iter().cloned())?;
+            match maxlag_delay(&json) {
+                Some(delay) if attempt < MAXLAG_RETRIES => {
+                    self.client.sleep(delay);
+                    attempt += 1;
+                },
+                _ => return api_result(json),
+            }
+        }
+    }
+
+    /// Sends a request to the action api and returns the response, which
+    /// may be an error.
+    fn query_once<'a, I>(&self, args: I) -> Result<serde_json::Value>
+            where I: Iterator<Item=(&'a str, &'a str)> + Clone {
+        let length: usize = args.clone().map(|(k, v)| k.len() + v.len() + 2).sum();
+        let response_str = if length > MAX_GET_LENGTH {
+            self.client.post(&self.base_url(), args)
+        } else {
+            self.client.get(&self.base_url(), args)
+        }.map_err(Error::HTTPError)?;
+        let json: serde_json::Value = serde_json::from_str(&response_str).map_err(Error::JSONError)?;
+        *self.warnings.lock().unwrap() = json
+            .as_object()
+            .and_then(|x| x.get("warnings"))
+            .and_then(|x| x.as_object())
+            .map(|x| x.iter().filter_map(|(module, warning)| Some((
+                module.clone(),
+                warning.as_object().and_then(|x| x.get("*")).and_then(|x| x.as_str())?.to_owned(),
+            ))).collect())
+            .unwrap_or_default();
         Ok(json)
     }
 
+    /// Gets and parses a json document from `url`, outside of the action
+    /// api, such as the rest api.
+    fn fetch_json(&self, url: &str) -> Result<serde_json::Value> {
+        let response_str = self.client.get(url, std::iter::empty()).map_err(Error::HTTPError)?;
+        serde_json::from_str(&response_str).map_err(Error::JSONError)
+    }
+
+    /// Returns the host of the api url, for example "en.wikipedia.org".
+    fn host(&self) -> String {
+        let base_url = self.base_url();
+        let url = base_url.split("://").last().unwrap_or("");
+        url.split('/').next().unwrap_or("").to_owned()
+    }
+
+    /// Returns the warnings included in the last response to a query, as
+    /// (`module`, `warning`). For example, they tell when a result was
+    /// truncated or a parameter is deprecated.
+    pub fn last_warnings(&self) -> Vec<(String, String)> {
+        self.warnings.lock().unwrap().clone()
+    }
+
     /// Searches for a string and returns a list of relevant page titles.
     ///
     /// # Examples
@@ -242,6 +558,162 @@ impl<A: http::HttpClient> Wikipedia<A> {
         Ok(results!(data, "search"))
     }
 
+    /// Like `search`, but skips the first `offset` results. Useful to fetch
+    /// the following pages of results.
+    pub fn search_offset(&self, query: &str, offset: u32) -> Result<Vec<String>> {
+        let results = &*format!("{}", self.search_results);
+        let offset = &*format!("{}", offset);
+        let data = self.query(vec![
+            ("list", "search"),
+            ("srprop", ""),
+            ("srlimit", results),
+            ("sroffset", offset),
+            ("srsearch", query),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        Ok(results!(data, "search"))
+    }
+
+    /// Like `search`, but also returns the total number of articles
+    /// matching `query`, which is usually more than the ones returned.
+    pub fn search_total(&self, query: &str) -> Result<(Vec<String>, u64)> {
+        let results = &*format!("{}", self.search_results);
+        let data = self.query(vec![
+            ("list", "search"),
+            ("srprop", ""),
+            ("srinfo", "totalhits"),
+            ("srlimit", results),
+            ("srsearch", query),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        let total = data.as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("searchinfo"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("totalhits"))
+            .and_then(|x| x.as_u64())
+            .ok_or(Error::JSONPathError)?;
+        Ok((results!(data, "search"), total))
+    }
+
+    /// Like `search`, but looks in the given `namespaces` instead of only
+    /// the articles, for example 10 for templates or 12 for help pages.
+    pub fn search_in_namespace(&self, query: &str, namespaces: &[i32]) -> Result<Vec<String>> {
+        let results = &*format!("{}", self.search_results);
+        let namespaces = namespaces.iter().map(|x| x.to_string()).collect::<Vec<_>>().join("|");
+        let data = self.query(vec![
+            ("list", "search"),
+            ("srprop", ""),
+            ("srlimit", results),
+            ("srnamespace", &*namespaces),
+            ("srsearch", query),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        Ok(results!(data, "search"))
+    }
+
+    /// Returns the correction suggested for a misspelled `query`, if any.
+    pub fn search_suggestion(&self, query: &str) -> Result<Option<String>> {
+        let data = self.query(vec![
+            ("list", "search"),
+            ("srinfo", "suggestion"),
+            ("srprop", ""),
+            ("srlimit", "1"),
+            ("srsearch", query),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        Ok(data.as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("searchinfo"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("suggestion"))
+            .and_then(|x| x.as_str())
+            .map(|x| x.to_owned()))
+    }
+
+    /// Like `search`, but also returns a snippet and some metadata of each
+    /// result.
+    pub fn search_detailed(&self, query: &str) -> Result<Vec<SearchResult>> {
+        let results = &*format!("{}", self.search_results);
+        let data = self.query(vec![
+            ("list", "search"),
+            ("srprop", "snippet|size|wordcount|timestamp"),
+            ("srlimit", results),
+            ("srsearch", query),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        Ok(data
+            .as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("search"))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?
+            .iter()
+            .filter_map(|x| {
+                let o = x.as_object()?;
+                let string = |key| o.get(key).and_then(|x| x.as_str()).map(|x| x.to_owned());
+                let number = |key| o.get(key).and_then(|x| x.as_u64());
+                Some(SearchResult {
+                    title: string("title")?,
+                    pageid: number("pageid")?,
+                    snippet: string("snippet")?,
+                    size: number("size")?,
+                    wordcount: number("wordcount")?,
+                    timestamp: string("timestamp")?,
+                })
+            })
+            .collect())
+    }
+
+    /// Returns the titles of the articles starting with `prefix`. Unlike
+    /// `search`, only titles are matched.
+    pub fn search_titles(&self, prefix: &str) -> Result<Vec<String>> {
+        let results = &*format!("{}", self.search_results);
+        let data = self.query(vec![
+            ("list", "prefixsearch"),
+            ("pslimit", results),
+            ("pssearch", prefix),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+
+        Ok(results!(data, "prefixsearch"))
+    }
+
+    /// Searches for articles whose title starts with `prefix` and returns a
+    /// list of (`title`, `url`).
+    pub fn opensearch(&self, prefix: &str) -> Result<Vec<(String, String)>> {
+        let results = &*format!("{}", self.search_results);
+        let data = self.query(vec![
+            ("search", prefix),
+            ("limit", results),
+            ("namespace", "0"),
+            ("format", "json"),
+            ("action", "opensearch"),
+        ].into_iter())?;
+
+        let data = data.as_array().ok_or(Error::JSONPathError)?;
+        let titles = data.get(1).and_then(|x| x.as_array()).ok_or(Error::JSONPathError)?;
+        let urls = data.get(3).and_then(|x| x.as_array()).ok_or(Error::JSONPathError)?;
+        Ok(titles.iter().zip(urls.iter()).filter_map(|(title, url)| Some((
+            title.as_str()?.to_owned(),
+            url.as_str()?.to_owned(),
+        ))).collect())
+    }
+
     /// Search articles within `radius` meters of `latitude` and `longitude`.
     ///
     /// # Examples
@@ -254,6 +726,36 @@ impl<A: http::HttpClient> Wikipedia<A> {
     /// assert!(results.contains(&"Madison Square Garden".to_owned()));
     /// ```
     pub fn geosearch(&self, latitude: f64, longitude: f64, radius: u16) -> Result<Vec<String>> {
+        let data = self.request_geosearch(latitude, longitude, radius)?;
+        Ok(results!(data, "geosearch"))
+    }
+
+    /// Like `geosearch`, but returns the coordinates of each article and its
+    /// distance to the center.
+    pub fn geosearch_detailed(&self, latitude: f64, longitude: f64, radius: u16) -> Result<Vec<GeoResult>> {
+        let data = self.request_geosearch(latitude, longitude, radius)?;
+        Ok(data
+            .as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("geosearch"))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?
+            .iter()
+            .filter_map(|x| {
+                let o = x.as_object()?;
+                Some(GeoResult {
+                    title: o.get("title").and_then(|x| x.as_str())?.to_owned(),
+                    pageid: o.get("pageid").and_then(|x| x.as_u64())?,
+                    lat: o.get("lat").and_then(|x| x.as_f64())?,
+                    lon: o.get("lon").and_then(|x| x.as_f64())?,
+                    dist: o.get("dist").and_then(|x| x.as_f64())?,
+                })
+            })
+            .collect())
+    }
+
+    fn request_geosearch(&self, latitude: f64, longitude: f64, radius: u16) -> Result<serde_json::Value> {
         if latitude < -90.0 || latitude > 90.0 {
             return Err(Error::InvalidParameter("latitude".to_string()))
         }
@@ -264,28 +766,82 @@ impl<A: http::HttpClient> Wikipedia<A> {
             return Err(Error::InvalidParameter("radius".to_string()))
         }
         let results = &*format!("{}", self.search_results);
-        let data = self.query(vec![
+        self.query(vec![
             ("list", "geosearch"),
             ("gsradius", &*format!("{}", radius)),
             ("gscoord", &*format!("{}|{}", latitude, longitude)),
             ("gslimit", results),
             ("format", "json"),
             ("action", "query"),
-        ].into_iter())?;
-        Ok(results!(data, "geosearch"))
+        ].into_iter())
+    }
+
+    /// Requests `count` random pages in `namespace`, sending as many
+    /// requests as needed, and returns the `random` entries.
+    fn request_random(&self, count: u32, namespace: i32, include_redirects: bool) -> Result<Vec<serde_json::Value>> {
+        let namespace = namespace.to_string();
+        let mut results = Vec::new();
+        let mut rncontinue: Option<String> = None;
+        while results.len() < count as usize {
+            let limit = (count - results.len() as u32).min(MAX_RANDOM_RESULTS).to_string();
+            let mut params = vec![
+                ("list", "random"),
+                ("rnnamespace", &*namespace),
+                ("rnlimit", &*limit),
+            ];
+            if include_redirects {
+                params.push(("rnfilterredir", "all"));
+            }
+            if let Some(ref rncontinue) = rncontinue {
+                params.push(("rncontinue", &**rncontinue));
+            }
+            params.push(("format", "json"));
+            params.push(("action", "query"));
+            let data = self.query(params.into_iter())?;
+            results.extend(data.as_object()
+                .and_then(|x| x.get("query"))
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("random"))
+                .and_then(|x| x.as_array())
+                .ok_or(Error::JSONPathError)?
+                .iter()
+                .cloned());
+            rncontinue = match data.as_object()
+                .and_then(|x| x.get("continue"))
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("rncontinue"))
+                .and_then(|x| x.as_str()) {
+                    Some(c) => Some(c.to_owned()),
+                    None => break,
+                };
+        }
+        results.truncate(count as usize);
+        Ok(results)
     }
 
     /// Fetches `count` random articles' title.
-    pub fn random_count(&self, count: u8) -> Result<Vec<String>> {
-        let data = self.query(vec![
-            ("list", "random"),
-            ("rnnamespace", "0"),
-            ("rnlimit", &*format!("{}", count)),
-            ("format", "json"),
-            ("action", "query"),
-        ].into_iter())?;
-        let r:Vec<String> = results!(data, "random");
-        Ok(r)
+    pub fn random_count(&self, count: u32) -> Result<Vec<String>> {
+        let data = self.request_random(count, 0, false)?;
+        Ok(data.iter().filter_map(random_title).collect())
+    }
+
+    /// Fetches `count` random articles, as (`pageid`, `title`).
+    pub fn random_count_with_pageids(&self, count: u32) -> Result<Vec<(u64, String)>> {
+        self.request_random(count, 0, false)?
+            .iter()
+            .map(|x| {
+                let id = x.as_object().and_then(|x| x.get("id")).and_then(|x| x.as_u64());
+                Ok((id.ok_or(Error::JSONPathError)?, random_title(x).ok_or(Error::JSONPathError)?))
+            })
+            .collect()
+    }
+
+    /// Fetches the title of `count` random pages in `namespace`, for example
+    /// 14 for categories or 6 for files. Redirects are skipped unless
+    /// `include_redirects` is true.
+    pub fn random_in_namespace(&self, count: u32, namespace: i32, include_redirects: bool) -> Result<Vec<String>> {
+        let data = self.request_random(count, namespace, include_redirects)?;
+        Ok(data.iter().filter_map(random_title).collect())
     }
 
     /// Fetches a random article's title.
@@ -293,67 +849,620 @@ impl<A: http::HttpClient> Wikipedia<A> {
         Ok(self.random_count(1)?.into_iter().next())
     }
 
-    /// Creates a new `Page` given a `title`.
-    pub fn page_from_title<'a>(&'a self, title: String) -> Page<'a, A> {
-        Page::from_title(self, title)
+    /// Returns the pageid of each of `titles` that exists, by title.
+    fn request_pageids(&self, titles: &[String]) -> Result<HashMap<String, u64>> {
+        let mut pageids = HashMap::new();
+        for titles in titles.chunks(MAX_TITLES) {
+            let data = self.query(vec![
+                ("format", "json"),
+                ("action", "query"),
+                ("titles", &*titles.join("|")),
+            ].into_iter())?;
+            let pages = data.as_object()
+                .and_then(|x| x.get("query"))
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("pages"))
+                .and_then(|x| x.as_object())
+                .ok_or(Error::JSONPathError)?;
+            pageids.extend(pages.values().filter_map(|page| {
+                let page = page.as_object()?;
+                Some((
+                    page.get("title")?.as_str()?.to_owned(),
+                    page.get("pageid")?.as_u64()?,
+                ))
+            }));
+        }
+        Ok(pageids)
     }
 
-    /// Creates a new `Page` given a `pageid`.
-    pub fn page_from_pageid<'a>(&'a self, pageid: String) -> Page<'a, A> {
-        Page::from_pageid(self, pageid)
+    /// Returns the canonical form of each of `titles`, as (`title`,
+    /// `normalized`). Titles that are already canonical are mapped to
+    /// themselves.
+    pub fn normalize_titles(&self, titles: &[String]) -> Result<Vec<(String, String)>> {
+        let mut normalized = HashMap::new();
+        for titles in titles.chunks(MAX_TITLES) {
+            let data = self.query(vec![
+                ("format", "json"),
+                ("action", "query"),
+                ("titles", &*titles.join("|")),
+            ].into_iter())?;
+            normalized.extend(title_mapping(&data, "normalized"));
+        }
+        Ok(titles.iter().map(|title| {
+            let n = normalized.get(title).unwrap_or(title);
+            (title.clone(), n.clone())
+        }).collect())
     }
-}
-
-#[derive(Debug)]
-enum TitlePageId {
-    Title(String),
-    PageId(String),
-}
 
-impl TitlePageId {
-    fn query_param(&self) -> (String, String) {
-        match *self {
-            TitlePageId::Title(ref s) => ("titles".to_owned(), s.clone()),
-            TitlePageId::PageId(ref s) => ("pageids".to_owned(), s.clone()),
-        }
+    /// Fetches the summary of each of `titles`, sending up to `concurrency`
+    /// requests at the same time. Returns a list of (`title`, `summary`) in
+    /// the same order as `titles`, where each summary may have failed on
+    /// its own.
+    pub fn summaries_parallel(&self, titles: &[String], concurrency: usize) -> Vec<(String, Result<String>)>
+    where
+        A: Sync,
+    {
+        let next = AtomicUsize::new(0);
+        let summaries = Mutex::new(titles.iter().map(|_| None).collect::<Vec<_>>());
+        thread::scope(|scope| {
+            for _ in 0..concurrency.max(1).min(titles.len()) {
+                scope.spawn(|| loop {
+                    let i = next.fetch_add(1, Ordering::SeqCst);
+                    let title = match titles.get(i) {
+                        Some(title) => title,
+                        None => break,
+                    };
+                    let summary = self.page_from_title(title.clone()).get_summary();
+                    summaries.lock().unwrap()[i] = Some(summary);
+                });
+            }
+        });
+        titles.iter().cloned()
+            .zip(summaries.into_inner().unwrap().into_iter().map(|x| x.unwrap_or(Err(Error::JSONPathError))))
+            .collect()
     }
-}
 
-#[derive(Debug)]
-pub struct Page<'a, A: 'a + http::HttpClient> {
-    wikipedia: &'a Wikipedia<A>,
-    identifier: TitlePageId,
-}
+    /// Fetches the summaries of several articles, requesting them 20 at a
+    /// time, the most the api returns at once.
+    /// Returns a list of (`title`, `summary`) where `title` is the one
+    /// provided in `titles`. Titles that do not exist are skipped.
+    pub fn summaries_from_titles(&self, titles: &[String]) -> Result<Vec<(String, String)>> {
+        let mut summaries = Vec::new();
+        for titles in titles.chunks(MAX_INTRO_EXTRACTS) {
+            let data = self.query(vec![
+                ("prop", "extracts"),
+                ("explaintext", ""),
+                ("exintro", ""),
+                ("exlimit", "max"),
+                ("redirects", ""),
+                ("format", "json"),
+                ("action", "query"),
+                ("titles", &*titles.join("|")),
+            ].into_iter())?;
 
-/// A wikipedia article.
-impl<'a, A: http::HttpClient> Page<'a, A> {
-    /// Creates a new `Page` given a `title`.
-    pub fn from_title(wikipedia: &'a Wikipedia<A>, title: String) -> Page<A> {
-        Page { wikipedia: wikipedia, identifier: TitlePageId::Title(title) }
-    }
+            let normalized = title_mapping(&data, "normalized");
+            let redirects = title_mapping(&data, "redirects");
+            let pages = data
+                .as_object()
+                .and_then(|x| x.get("query"))
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("pages"))
+                .and_then(|x| x.as_object())
+                .ok_or(Error::JSONPathError)?;
+            let mut extracts = HashMap::new();
+            for page in pages.values() {
+                let page = match page.as_object() {
+                    Some(p) => p,
+                    None => continue,
+                };
+                let title = page.get("title").and_then(|x| x.as_str());
+                let extract = page.get("extract").and_then(|x| x.as_str());
+                if let (Some(title), Some(extract)) = (title, extract) {
+                    extracts.insert(title.to_owned(), extract.to_owned());
+                }
+            }
 
-    /// Creates a new `Page` given a `pageid`.
-    pub fn from_pageid(wikipedia: &'a Wikipedia<A>, pageid: String) -> Page<A> {
-        Page { wikipedia: wikipedia, identifier: TitlePageId::PageId(pageid) }
+            summaries.extend(titles.iter().filter_map(|title| {
+                let mut resolved = title;
+                if let Some(t) = normalized.get(resolved) {
+                    resolved = t;
+                }
+                if let Some(t) = redirects.get(resolved) {
+                    resolved = t;
+                }
+                extracts.get(resolved).map(|e| (title.clone(), e.clone()))
+            }));
+        }
+        Ok(summaries)
     }
 
-    /// Gets the `Page`'s `pageid`.
-    pub fn get_pageid(&self) -> Result<String> {
-        match self.identifier {
-            TitlePageId::PageId(ref s) => Ok(s.clone()),
-            TitlePageId::Title(_) => {
-                let qp = self.identifier.query_param();
-                let q = self.wikipedia.query(vec![
-                    ("prop", "info|pageprops"),
-                    ("inprop", "url"),
-                    ("ppprop", "disambiguation"),
+    /// Renders `wikitext` to html, the same way an article would be.
+    pub fn parse_wikitext(&self, wikitext: &str) -> Result<String> {
+        let q = self.query(vec![
+            ("prop", "text"),
+            ("contentmodel", "wikitext"),
+            ("text", wikitext),
+            ("format", "json"),
+            ("action", "parse"),
+        ].into_iter())?;
+
+        Ok(q
+            .as_object()
+            .and_then(|x| x.get("parse"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("text"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("*"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
+    }
+
+    /// Expands the templates in `wikitext`, returning the resulting
+    /// wikitext.
+    pub fn expand_templates(&self, wikitext: &str) -> Result<String> {
+        let q = self.query(vec![
+            ("prop", "wikitext"),
+            ("text", wikitext),
+            ("format", "json"),
+            ("action", "expandtemplates"),
+        ].into_iter())?;
+
+        Ok(q
+            .as_object()
+            .and_then(|x| x.get("expandtemplates"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("wikitext"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
+    }
+
+    /// Creates an iterator to view all members of a category. `category`
+    /// is the full title of the category, for example
+    /// "Category:Programming languages".
+    pub fn category_members<'a>(&'a self, category: &str) -> Result<Iter<'a, A, iter::CategoryMember>> {
+        Iter::new(&self.page_from_title(category.to_owned()))
+    }
+
+    /// Creates a new `Page` given a `title`.
+    pub fn page_from_title<'a>(&'a self, title: String) -> Page<'a, A> {
+        Page::from_title(self, title)
+    }
+
+    /// Creates a new `Page` given a `pageid`.
+    pub fn page_from_pageid<'a>(&'a self, pageid: String) -> Page<'a, A> {
+        Page::from_pageid(self, pageid)
+    }
+
+    /// Creates a new `Page` from the url of an article, such as
+    /// "https://en.wikipedia.org/wiki/Law_of_triviality" or its mobile
+    /// version "https://en.m.wikipedia.org/wiki/Law_of_triviality". If the
+    /// url is for another language, the `Page` uses a copy of this
+    /// `Wikipedia` set to that language.
+    pub fn page_from_url<'a>(&'a self, url: &str) -> Result<Page<'a, A>>
+    where
+        A: Clone,
+    {
+        let invalid = || Error::InvalidParameter("url".to_string());
+        let url = url.split('#').next().unwrap_or("");
+        let url = url.split('?').next().unwrap_or("");
+        let url = url.split("://").last().unwrap_or("");
+        let (host, path) = url.split_at(url.find('/').ok_or_else(invalid)?);
+        let title = path.strip_prefix("/wiki/").ok_or_else(invalid)?;
+        let title = decode_path_segment(title).ok_or_else(invalid)?.replace('_', " ");
+        if title.is_empty() {
+            return Err(invalid());
+        }
+        // mobile urls have an extra "m" subdomain after the language
+        let host = match host.split_once(".m.") {
+            Some((language, rest)) if !language.contains('.') => format!("{}.{}", language, rest),
+            _ => host.to_owned(),
+        };
+        if host == self.host() {
+            return Ok(self.page_from_title(title));
+        }
+        let suffix = self.post_language_url.split('/').next().unwrap_or("");
+        let language = match host.as_str().strip_suffix(suffix) {
+            Some(language) if !suffix.is_empty() && !language.is_empty() => language,
+            _ => return Err(invalid()),
+        };
+        let wikipedia = self.clone().with_language(language);
+        Ok(Page {
+            wikipedia: WikipediaRef::Owned(Arc::new(wikipedia)),
+            identifier: TitlePageId::Title(title),
+            follow_redirects: true,
+        })
+    }
+}
+
+/// An article found by `Wikipedia::search_detailed`.
+#[derive(Debug, PartialEq)]
+pub struct SearchResult {
+    pub title: String,
+    pub pageid: u64,
+    /// Html fragment of the text matching the query, with the matches
+    /// wrapped in `<span class="searchmatch">`
+    pub snippet: String,
+    /// Size of the article in bytes
+    pub size: u64,
+    pub wordcount: u64,
+    /// Time of the last edit, for example "2018-03-04T10:20:00Z"
+    pub timestamp: String,
+}
+
+/// An article found by `Wikipedia::geosearch_detailed`.
+#[derive(Debug, PartialEq)]
+pub struct GeoResult {
+    pub title: String,
+    pub pageid: u64,
+    pub lat: f64,
+    pub lon: f64,
+    /// Distance in meters to the searched coordinates.
+    pub dist: f64,
+}
+
+/// A section of an article, as returned by `Page::get_sections_detailed`.
+#[derive(Debug, PartialEq)]
+pub struct Section {
+    /// The section heading
+    pub line: String,
+    /// The heading level, 2 for `== Heading ==`, 3 for `=== Heading ===`, etc.
+    pub level: u32,
+    /// The position in the table of contents, for example "2.1"
+    pub number: String,
+    /// The anchor to link to the section
+    pub anchor: String,
+    /// The section index used by the parse API
+    pub index: String,
+}
+
+/// Basic metadata of an article, as returned by `Page::get_info`.
+#[derive(Debug, PartialEq)]
+pub struct PageInfo {
+    pub pageid: u64,
+    pub title: String,
+    /// Size of the article in bytes
+    pub length: u64,
+    /// Time the page was last touched, for example "2018-03-04T10:20:00Z"
+    pub touched: String,
+    /// The namespace, 0 for articles
+    pub ns: i32,
+    /// True if the requested title was a redirect to this page
+    pub redirect: bool,
+}
+
+/// How section headings are written in the content returned by
+/// `Page::get_content_formatted`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SectionFormat {
+    /// No marks, the heading is just a line of text
+    Plain,
+    /// As in wikitext, for example `== Heading ==`. This is what
+    /// `Page::get_content` uses.
+    Wiki,
+    /// As a marker with the level of the heading, for example
+    /// `\u{1}\u{2}2\u{2}\u{1}Heading`
+    Raw,
+}
+
+impl SectionFormat {
+    fn as_str(&self) -> &'static str {
+        match *self {
+            SectionFormat::Plain => "plain",
+            SectionFormat::Wiki => "wiki",
+            SectionFormat::Raw => "raw",
+        }
+    }
+}
+
+/// How the title or pageid of a `Page` was resolved, as returned by
+/// `Page::resolve`.
+#[derive(Debug, PartialEq)]
+pub struct ResolvedPage {
+    /// The title or pageid the `Page` was created with
+    pub requested: String,
+    /// The canonical form of the requested title, if it was not already
+    pub normalized: Option<String>,
+    /// The title the requested one redirects to, if any
+    pub redirected_to: Option<String>,
+    pub final_title: String,
+    /// `None` if the page does not exist
+    pub pageid: Option<u64>,
+}
+
+/// A restriction on a page, as returned by `Page::get_protection`.
+#[derive(Debug, PartialEq)]
+pub struct Protection {
+    /// The restricted action, for example "edit" or "move"
+    pub kind: String,
+    /// The group allowed to do it, for example "autoconfirmed" or "sysop"
+    pub level: String,
+    /// When the protection ends, "infinity" if it does not
+    pub expiry: String,
+}
+
+/// A point associated to an article, as returned by
+/// `Page::get_all_coordinates`.
+#[derive(Debug, PartialEq)]
+pub struct Coordinate {
+    pub lat: f64,
+    pub lon: f64,
+    /// The celestial body the point is on, for example "earth" or "mars"
+    pub globe: String,
+    /// True if it is the main location of the subject of the article
+    pub primary: bool,
+    /// The `type` of the place, for example "city" or "landmark"
+    pub kind: Option<String>,
+    /// Approximate size of the place in meters
+    pub dim: Option<u64>,
+}
+
+/// Summary of an article from the rest api, as returned by
+/// `Page::get_rest_summary`.
+#[derive(Debug, PartialEq)]
+pub struct RestSummary {
+    pub title: String,
+    /// Short description of the subject, for example "American rock band"
+    pub description: Option<String>,
+    /// First paragraph of the article, in plain text
+    pub extract: String,
+    pub thumbnail_url: Option<String>,
+    /// Url of the article for each platform, "desktop" and "mobile"
+    pub content_urls: HashMap<String, String>,
+}
+
+/// What is needed to show a preview of an article, as returned by
+/// `Page::get_preview`.
+#[derive(Debug, PartialEq)]
+pub struct Preview {
+    pub title: String,
+    /// Short description of the subject, for example "American rock band"
+    pub description: Option<String>,
+    /// First paragraph of the article, in plain text
+    pub extract: String,
+    /// Url of the main image, up to 320 pixels wide
+    pub thumbnail: Option<String>,
+}
+
+/// Converts a number of days since 1970-01-01 to a date formatted as
+/// "YYYYMMDD".
+fn format_days(days: i64) -> String {
+    // http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}{:02}{:02}", year, month, day)
+}
+
+/// Escapes `s` to be used as a segment of a url path.
+fn encode_path_segment(s: &str) -> String {
+    s.bytes().map(|b| match b {
+        b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (b as char).to_string(),
+        _ => format!("%{:02X}", b),
+    }).collect()
+}
+
+/// Reverses `encode_path_segment`, returning `None` if `s` is not a valid
+/// percent-encoded utf-8 string.
+fn decode_path_segment(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
+
+/// Reads the title of an entry of a `list=random` response.
+fn random_title(entry: &serde_json::Value) -> Option<String> {
+    entry.as_object()
+        .and_then(|x| x.get("title"))
+        .and_then(|x| x.as_str())
+        .map(|x| x.to_owned())
+}
+
+/// Reads the content of the first revision of `page`, from its main slot
+/// or, as older servers return it, from the revision itself.
+fn revision_content(page: &serde_json::Value) -> Option<&str> {
+    let revision = page.as_object()
+        .and_then(|x| x.get("revisions"))
+        .and_then(|x| x.as_array())
+        .and_then(|x| x.iter().next())
+        .and_then(|x| x.as_object())?;
+    revision.get("slots")
+        .and_then(|x| x.as_object())
+        .and_then(|x| x.get("main"))
+        .and_then(|x| x.as_object())
+        .unwrap_or(revision)
+        .get("*")
+        .and_then(|x| x.as_str())
+}
+
+/// Returns `Error::InvalidTitle` if the server marked `page` as invalid.
+fn check_title(page: &serde_json::Map<String, serde_json::Value>) -> Result<()> {
+    if !page.contains_key("invalid") {
+        return Ok(());
+    }
+    Err(Error::InvalidTitle(
+        page.get("invalidreason")
+            .and_then(|x| x.as_str())
+            .unwrap_or("")
+            .to_owned()))
+}
+
+/// Returns the `error` in an action api response as an `Error::ApiError`.
+fn api_result(json: serde_json::Value) -> Result<serde_json::Value> {
+    if let Some(error) = json.as_object().and_then(|x| x.get("error")).and_then(|x| x.as_object()) {
+        let field = |key| error.get(key).and_then(|x| x.as_str()).unwrap_or("").to_owned();
+        return Err(Error::ApiError { code: field("code"), info: field("info") });
+    }
+    Ok(json)
+}
+
+/// If the response is a `maxlag` error, returns how long to wait before
+/// retrying: the lag reported by the api, or `MAXLAG_DELAY` if it is
+/// missing.
+fn maxlag_delay(json: &serde_json::Value) -> Option<Duration> {
+    let error = json.as_object()
+        .and_then(|x| x.get("error"))
+        .and_then(|x| x.as_object())
+        .filter(|x| x.get("code").and_then(|x| x.as_str()) == Some("maxlag"))?;
+    Some(error.get("lag")
+        .and_then(|x| x.as_f64())
+        .filter(|x| *x > 0.0)
+        .map(Duration::from_secs_f64)
+        .unwrap_or(Duration::from_secs(MAXLAG_DELAY)))
+}
+
+/// Reads a list of `from`/`to` pairs (such as `normalized` or `redirects`)
+/// from a query response into a map.
+fn title_mapping(data: &serde_json::Value, field: &str) -> HashMap<String, String> {
+    data.as_object()
+        .and_then(|x| x.get("query"))
+        .and_then(|x| x.as_object())
+        .and_then(|x| x.get(field))
+        .and_then(|x| x.as_array())
+        .map(|x| x.iter().filter_map(|i| {
+            let i = i.as_object()?;
+            Some((
+                i.get("from").and_then(|x| x.as_str())?.to_owned(),
+                i.get("to").and_then(|x| x.as_str())?.to_owned(),
+            ))
+        }).collect())
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone)]
+enum TitlePageId {
+    Title(String),
+    PageId(String),
+}
+
+impl TitlePageId {
+    fn query_param(&self) -> (String, String) {
+        match *self {
+            TitlePageId::Title(ref s) => ("titles".to_owned(), s.clone()),
+            TitlePageId::PageId(ref s) => ("pageids".to_owned(), s.clone()),
+        }
+    }
+}
+
+/// The `Wikipedia` a `Page` belongs to. It is usually borrowed, but a `Page`
+/// can own one when it was derived from another `Page`, like with
+/// `Page::in_language`.
+#[derive(Debug)]
+enum WikipediaRef<'a, A: 'a + http::HttpClient> {
+    Borrowed(&'a Wikipedia<A>),
+    Owned(Arc<Wikipedia<A>>),
+}
+
+impl<'a, A: http::HttpClient> Clone for WikipediaRef<'a, A> {
+    fn clone(&self) -> Self {
+        match *self {
+            WikipediaRef::Borrowed(w) => WikipediaRef::Borrowed(w),
+            WikipediaRef::Owned(ref w) => WikipediaRef::Owned(w.clone()),
+        }
+    }
+}
+
+impl<'a, A: http::HttpClient> Deref for WikipediaRef<'a, A> {
+    type Target = Wikipedia<A>;
+
+    fn deref(&self) -> &Wikipedia<A> {
+        match *self {
+            WikipediaRef::Borrowed(w) => w,
+            WikipediaRef::Owned(ref w) => w,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Page<'a, A: 'a + http::HttpClient> {
+    wikipedia: WikipediaRef<'a, A>,
+    identifier: TitlePageId,
+    /// If false, redirect pages are returned as they are instead of being
+    /// replaced by their target.
+    follow_redirects: bool,
+}
+
+impl<'a, A: http::HttpClient> Clone for Page<'a, A> {
+    fn clone(&self) -> Self {
+        Page {
+            wikipedia: self.wikipedia.clone(),
+            identifier: self.identifier.clone(),
+            follow_redirects: self.follow_redirects,
+        }
+    }
+}
+
+/// A wikipedia article.
+impl<'a, A: http::HttpClient> Page<'a, A> {
+    /// Creates a new `Page` given a `title`.
+    pub fn from_title(wikipedia: &'a Wikipedia<A>, title: String) -> Page<A> {
+        Page {
+            wikipedia: WikipediaRef::Borrowed(wikipedia),
+            identifier: TitlePageId::Title(title),
+            follow_redirects: true,
+        }
+    }
+
+    /// Creates a new `Page` given a `pageid`.
+    pub fn from_pageid(wikipedia: &'a Wikipedia<A>, pageid: String) -> Page<A> {
+        Page {
+            wikipedia: WikipediaRef::Borrowed(wikipedia),
+            identifier: TitlePageId::PageId(pageid),
+            follow_redirects: true,
+        }
+    }
+
+    /// Sets whether redirects are followed and returns `self`. They are
+    /// by default; when they are not, a redirect is handled like any other
+    /// page, and `get_redirect_target` tells where it points to.
+    pub fn with_follow_redirects(mut self, follow_redirects: bool) -> Self {
+        self.follow_redirects = follow_redirects;
+        self
+    }
+
+    /// Sends a query about the `Page`, leaving out the `redirects` parameter
+    /// if redirects should not be followed.
+    fn query<'b, I>(&self, args: I) -> Result<serde_json::Value>
+            where I: Iterator<Item=(&'b str, &'b str)> {
+        let follow_redirects = self.follow_redirects;
+        self.wikipedia.query(args.filter(|&(k, _)| follow_redirects || k != "redirects"))
+    }
+
+    /// Gets the `Page`'s `pageid`.
+    pub fn get_pageid(&self) -> Result<String> {
+        match self.identifier {
+            TitlePageId::PageId(ref s) => Ok(s.clone()),
+            TitlePageId::Title(_) => {
+                let qp = self.identifier.query_param();
+                let q = self.query(vec![
+                    ("prop", "info|pageprops"),
+                    ("inprop", "url"),
+                    ("ppprop", "disambiguation"),
                     ("redirects", ""),
                     ("format", "json"),
                     ("action", "query"),
                     (&*qp.0, &*qp.1),
                 ].into_iter())?;
 
-                match self.redirect(&q) {
+                match self.redirect(&q)? {
                     Some(r) => return Page::from_title(&self.wikipedia, r).get_pageid(),
                     None => (),
                 }
@@ -364,7 +1473,9 @@ impl<'a, A: http::HttpClient> Page<'a, A> {
                     .and_then(|x| x.get("pages"))
                     .and_then(|x| x.as_object())
                     .ok_or(Error::JSONPathError)?;
-                pages.keys().cloned().next().ok_or(Error::JSONPathError)
+                let (pageid, page) = pages.iter().next().ok_or(Error::JSONPathError)?;
+                check_title(page.as_object().ok_or(Error::JSONPathError)?)?;
+                Ok(pageid.clone())
             }
         }
     }
@@ -375,7 +1486,7 @@ impl<'a, A: http::HttpClient> Page<'a, A> {
             TitlePageId::Title(ref s) => Ok(s.clone()),
             TitlePageId::PageId(_) => {
                 let qp = self.identifier.query_param();
-                let q = self.wikipedia.query(vec![
+                let q = self.query(vec![
                     ("prop", "info|pageprops"),
                     ("inprop", "url"),
                     ("ppprop", "disambiguation"),
@@ -385,7 +1496,7 @@ impl<'a, A: http::HttpClient> Page<'a, A> {
                     (&*qp.0, &*qp.1),
                 ].into_iter())?;
 
-                match self.redirect(&q) {
+                match self.redirect(&q)? {
                     Some(r) => return Ok(r),
                     None => (),
                 }
@@ -400,8 +1511,9 @@ impl<'a, A: http::HttpClient> Page<'a, A> {
                     Some(p) => p,
                     None => return Err(Error::JSONPathError),
                 };
-                Ok(page.as_object()
-                    .and_then(|x| x.get("title"))
+                let page = page.as_object().ok_or(Error::JSONPathError)?;
+                check_title(page)?;
+                Ok(page.get("title")
                     .and_then(|x| x.as_str())
                     .ok_or(Error::JSONPathError)?
                     .to_owned())
@@ -409,797 +1521,3597 @@ impl<'a, A: http::HttpClient> Page<'a, A> {
         }
     }
 
-    /// If the `Page` redirects to another one it returns its title, otherwise
-    /// returns None.
-    fn redirect(&self, q: &serde_json::Value) -> Option<String> {
-        q.as_object()
-            .and_then(|x| x.get("query"))
-            .and_then(|x| x.as_object())
-            .and_then(|x| x.get("redirects"))
-            .and_then(|x| x.as_array())
-            .and_then(|x| x.into_iter().next())
+    /// Fetches the `info` and `pageprops` of the `Page`, following redirects,
+    /// and returns the resolved page object.
+    fn request_info(&self) -> Result<serde_json::Map<String, serde_json::Value>> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "info|pageprops"),
+            ("inprop", "url"),
+            ("ppprop", "disambiguation"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            // mark the target the same way the api marks redirect pages
+            let mut info = Page::from_title(&self.wikipedia, r).request_info()?;
+            info.insert("redirect".to_owned(), serde_json::Value::String("".to_owned()));
+            return Ok(info);
+        }
+
+        self.get_first_page(&q)
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("to"))
+            .cloned()
+            .ok_or(Error::JSONPathError)
+    }
+
+    /// Gets the title of the page the `Page` resolves to, after
+    /// normalization and redirects, as needed by the rest apis.
+    fn resolved_title(&self) -> Result<String> {
+        let info = self.request_info()?;
+        check_title(&info)?;
+        Ok(info.get("title")
             .and_then(|x| x.as_str())
-            .map(|x| x.to_owned())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    /// Given a parsed response, usually we access the first page with the data
-    fn get_first_page<'parsed>(&self, data: &'parsed serde_json::Value) -> Option<&'parsed serde_json::Value> {
-        let pages = data
-            .as_object()
-            .and_then(|x| x.get("query"))
+    /// Gets the basic metadata of the `Page`.
+    pub fn get_info(&self) -> Result<PageInfo> {
+        let info = self.request_info()?;
+        Ok(PageInfo {
+            pageid: info.get("pageid").and_then(|x| x.as_u64()).ok_or(Error::JSONPathError)?,
+            title: info.get("title").and_then(|x| x.as_str()).ok_or(Error::JSONPathError)?.to_owned(),
+            length: info.get("length").and_then(|x| x.as_u64()).ok_or(Error::JSONPathError)?,
+            touched: info.get("touched").and_then(|x| x.as_str()).ok_or(Error::JSONPathError)?.to_owned(),
+            ns: info.get("ns").and_then(|x| x.as_i64()).ok_or(Error::JSONPathError)? as i32,
+            redirect: info.contains_key("redirect"),
+        })
+    }
+
+    /// Returns true if the `Page` is a disambiguation page.
+    pub fn is_disambiguation(&self) -> Result<bool> {
+        Ok(self.request_info()?
+            .get("pageprops")
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("pages"))
-            .and_then(|x| x.as_object());
-        let pageid = match pages {
-            Some(some_pages) => match some_pages.keys().next() {
-                Some(pageid) => pageid,
-                None => return None,
-            },
-            None => return None,
-        };
-        pages.unwrap().get(pageid)
+            .map(|x| x.contains_key("disambiguation"))
+            .unwrap_or(false))
     }
 
-    /// Gets the markdown content of the article.
-    pub fn get_content(&self) -> Result<String> {
+    /// Returns false if the `Page` does not exist, or
+    /// `Error::InvalidTitle` if its title is not valid.
+    pub fn exists(&self) -> Result<bool> {
+        let info = self.request_info()?;
+        check_title(&info)?;
+        Ok(!info.contains_key("missing"))
+    }
+
+    /// Gets the title of the `Page` as it is displayed, which may include
+    /// html formatting, for example "<i>Drosophila melanogaster</i>".
+    pub fn get_display_title(&self) -> Result<String> {
         let qp = self.identifier.query_param();
-        let q = self.wikipedia.query(vec![
-            ("prop", "extracts|revisions"),
-            ("explaintext", ""),
-            ("rvprop", "ids"),
+        let q = self.query(vec![
+            ("prop", "info"),
+            ("inprop", "displaytitle"),
             ("redirects", ""),
             ("format", "json"),
             ("action", "query"),
             (&*qp.0, &*qp.1),
         ].into_iter())?;
 
-        match self.redirect(&q) {
-            Some(r) => return Page::from_title(&self.wikipedia, r).get_content(),
-            None => (),
-        };
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_display_title();
+        }
 
         Ok(self.get_first_page(&q)
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.get("displaytitle"))
             .and_then(|x| x.as_str())
             .ok_or(Error::JSONPathError)?
             .to_owned())
     }
 
-    /// Gets the html content of the article.
-    pub fn get_html_content(&self) -> Result<String> {
+    /// Gets the short description of the `Page`, the one-line subtitle
+    /// such as "American rock band". Returns `None` if it has none.
+    pub fn get_description(&self) -> Result<Option<String>> {
         let qp = self.identifier.query_param();
-        let q = self.wikipedia.query(vec![
-            ("prop", "revisions"),
-            ("rvprop", "content"),
-            ("rvlimit", "1"),
-            ("rvparse", ""),
+        let q = self.query(vec![
+            ("prop", "pageprops"),
+            ("ppprop", "wikibase-shortdesc"),
             ("redirects", ""),
             ("format", "json"),
             ("action", "query"),
             (&*qp.0, &*qp.1),
         ].into_iter())?;
 
-        match self.redirect(&q) {
-            Some(r) => return Page::from_title(&self.wikipedia, r).get_html_content(),
-            None => (),
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_description();
         }
 
-        Ok(self.get_first_page(&q)
+        let page = self.get_first_page(&q)
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("revisions"))
-            .and_then(|x| x.as_array())
-            .and_then(|x| x.into_iter().next())
+            .ok_or(Error::JSONPathError)?;
+        Ok(page.get("pageprops")
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("*"))
+            .and_then(|x| x.get("wikibase-shortdesc"))
             .and_then(|x| x.as_str())
-            .ok_or(Error::JSONPathError)?
-            .to_owned())
+            .map(|x| x.to_owned()))
     }
 
-    /// Gets a summary of the article.
-    pub fn get_summary(&self) -> Result<String> {
+    /// Gets the protections applied to the `Page`.
+    pub fn get_protection(&self) -> Result<Vec<Protection>> {
         let qp = self.identifier.query_param();
-        let q = self.wikipedia.query(vec![
-            ("prop", "extracts"),
-            ("explaintext", ""),
-            ("exintro", ""),
+        let q = self.query(vec![
+            ("prop", "info"),
+            ("inprop", "protection"),
             ("redirects", ""),
             ("format", "json"),
             ("action", "query"),
             (&*qp.0, &*qp.1),
         ].into_iter())?;
 
-        match self.redirect(&q) {
-            Some(r) => return Page::from_title(&self.wikipedia, r).get_summary(),
-            None => (),
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_protection();
         }
 
-        Ok(self.get_first_page(&q)
+        self.get_first_page(&q)
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.get("protection"))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?
+            .iter()
+            .map(|x| {
+                let x = x.as_object().ok_or(Error::JSONPathError)?;
+                let field = |key| x.get(key).and_then(|x| x.as_str()).map(|x| x.to_owned()).ok_or(Error::JSONPathError);
+                Ok(Protection {
+                    kind: field("type")?,
+                    level: field("level")?,
+                    expiry: field("expiry")?,
+                })
+            })
+            .collect()
+    }
+
+    /// Gets the full url of the `Page`.
+    pub fn get_url(&self) -> Result<String> {
+        Ok(self.request_info()?
+            .get("fullurl")
             .and_then(|x| x.as_str())
             .ok_or(Error::JSONPathError)?
             .to_owned())
     }
 
-    /// Receive a json object and extracts any `continue` parameters to be
-    /// used when browsing following pages.
-    fn parse_cont(&self, q: &serde_json::Value) -> Result<Option<Vec<(String, String)>>> {
-        let cont = match q
-            .as_object()
-            .and_then(|x| x.get("continue"))
-            .and_then(|x| x.as_object()) {
-            Some(v) => v,
-            None => return Ok(None),
-        };
-        let mut cont_v = vec![];
-        for (k, v) in cont.into_iter() {
-            let value = match *v {
-                serde_json::Value::Null => "".to_owned(),
-                serde_json::Value::Bool(b) => if b { "1" } else { "0" }.to_owned(),
-                serde_json::Value::Number(ref f) => format!("{}", f),
-                serde_json::Value::String(ref s) => s.clone(),
-                _ => return Err(Error::JSONPathError),
-            };
-            cont_v.push((k.clone(), value));
-        }
-        Ok(Some(cont_v))
-    }
-
-    fn request_images(&self, cont: &Option<Vec<(String, String)>>) ->
-            Result<(Vec<serde_json::Value>, Option<Vec<(String, String)>>)> {
-        cont!(self, cont,
-            ("generator", "images"),
-            ("gimlimit", &*self.wikipedia.images_results),
-            ("prop", "imageinfo"),
-            ("iiprop", "url")
-        )
+    /// Gets the canonical url of the `Page`.
+    pub fn get_canonical_url(&self) -> Result<String> {
+        Ok(self.request_info()?
+            .get("canonicalurl")
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    /// Creates an iterator to view all images in the `Page`.
-    pub fn get_images(&self) -> Result<Iter<A, iter::Image>> {
-        Iter::new(&self)
-    }
+    /// Finds the page the `Page` refers to, telling whether its title was
+    /// normalized or redirected on the way.
+    pub fn resolve(&self) -> Result<ResolvedPage> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "info"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
 
-    fn request_extlinks(&self, cont: &Option<Vec<(String, String)>>) ->
-            Result<(Vec<serde_json::Value>, Option<Vec<(String, String)>>)> {
-        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
-            ("prop", "extlinks"),
-            ("ellimit", &*self.wikipedia.links_results)
-        );
-        a.map(|(pages, cont)| {
-            let page = match pages.into_iter().next() {
-                Some(p) => p,
-                None => return (Vec::new(), None),
-            };
-            (page
-                .as_object()
-                .and_then(|x| x.get("extlinks"))
-                .and_then(|x| x.as_array())
-                .map(|x| x.into_iter().cloned().collect())
-                .unwrap_or(Vec::new()), cont)
+        let normalized = title_mapping(&q, "normalized").remove(&qp.1);
+        let redirected_to = self.redirect(&q)?;
+        let page = self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?;
+        Ok(ResolvedPage {
+            requested: qp.1.clone(),
+            normalized,
+            redirected_to,
+            final_title: page.get("title").and_then(|x| x.as_str()).ok_or(Error::JSONPathError)?.to_owned(),
+            pageid: page.get("pageid").and_then(|x| x.as_u64()),
         })
     }
 
-    /// Creates an iterator to view all references (external links) in the `Page`.
-    pub fn get_references(&self) -> Result<Iter<A, iter::Reference>> {
-        Iter::new(&self)
-    }
-
-    fn request_links(&self, cont: &Option<Vec<(String, String)>>) ->
-            Result<(Vec<serde_json::Value>, Option<Vec<(String, String)>>)> {
-        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
-            ("prop", "links"),
-            ("plnamespace", "0"),
-            ("ellimit", &*self.wikipedia.links_results)
-        );
-        a.map(|(pages, cont)| {
-            let page = match pages.into_iter().next() {
-                Some(p) => p,
-                None => return (Vec::new(), None),
-            };
-            (page
-                .as_object()
-                .and_then(|x| x.get("links"))
-                .and_then(|x| x.as_array())
-                .map(|x| x.into_iter().cloned().collect())
-                .unwrap_or(Vec::new()), cont)
-        })
+    /// Gets the canonical form of the `Page`'s title, for example
+    /// "Law of triviality" for "law_of_triviality". Unlike `get_title`,
+    /// redirects are never followed.
+    pub fn normalized_title(&self) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let q = self.wikipedia.query(vec![
+            ("prop", "info"),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+        let page = self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?;
+        check_title(page)?;
+        Ok(page.get("title")
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    /// Creates an iterator to view all internal links in the `Page`.
-    pub fn get_links(&self) -> Result<Iter<A, iter::Link>> {
-        Iter::new(&self)
+    /// Gets the title the `Page` redirects to, or `None` if it is not a
+    /// redirect.
+    pub fn get_redirect_target(&self) -> Result<Option<String>> {
+        let qp = self.identifier.query_param();
+        let q = self.wikipedia.query(vec![
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+        self.redirect(&q)
     }
 
-    fn request_categories(&self, cont: &Option<Vec<(String, String)>>) ->
-            Result<(Vec<serde_json::Value>, Option<Vec<(String, String)>>)> {
-        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
-            ("prop", "categories"),
-            ("cllimit", &*self.wikipedia.categories_results)
-        );
-        a.map(|(pages, cont)| {
-            let page = match pages.into_iter().next() {
-                Some(p) => p,
-                None => return (Vec::new(), None),
-            };
-            (page
-                .as_object()
-                .and_then(|x| x.get("categories"))
+    /// If the `Page` redirects to another one it returns its title, otherwise
+    /// returns None. Chains of redirects are followed to the last one.
+    fn redirect(&self, q: &serde_json::Value) -> Result<Option<String>> {
+        let mut title = match q.as_object()
+                .and_then(|x| x.get("query"))
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("redirects"))
                 .and_then(|x| x.as_array())
-                .map(|x| x.into_iter().cloned().collect())
-                .unwrap_or(Vec::new()), cont)
-        })
+                .and_then(|x| x.iter().next())
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("to"))
+                .and_then(|x| x.as_str()) {
+            Some(t) => t.to_owned(),
+            None => return Ok(None),
+        };
+        let redirects = title_mapping(q, "redirects");
+        let mut seen = HashSet::new();
+        seen.insert(title.clone());
+        while let Some(to) = redirects.get(&title) {
+            if !seen.insert(to.clone()) || seen.len() > MAX_REDIRECTS {
+                return Err(Error::RedirectLoop(title));
+            }
+            title = to.clone();
+        }
+        Ok(Some(title))
     }
 
-    /// Creates an iterator to view all categories of the `Page`.
-    pub fn get_categories(&self) -> Result<Iter<A, iter::Category>> {
-        Iter::new(&self)
+    /// Given a parsed response, usually we access the first page with the data
+    fn get_first_page<'parsed>(&self, data: &'parsed serde_json::Value) -> Option<&'parsed serde_json::Value> {
+        let pages = data
+            .as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("pages"))
+            .and_then(|x| x.as_object());
+        let pageid = match pages {
+            Some(some_pages) => match some_pages.keys().next() {
+                Some(pageid) => pageid,
+                None => return None,
+            },
+            None => return None,
+        };
+        pages.unwrap().get(pageid)
     }
 
-    fn request_langlinks(&self, cont: &Option<Vec<(String, String)>>) ->
-            Result<(Vec<serde_json::Value>, Option<Vec<(String, String)>>)> {
-        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
-            ("prop", "langlinks"),
-            ("lllimit", &*self.wikipedia.links_results)
-        );
-        a.map(|(pages, cont)| {
-            let page = match pages.into_iter().next() {
-                Some(p) => p,
-                None => return (Vec::new(), None),
-            };
-            (page
-                .as_object()
-                .and_then(|x| x.get("langlinks"))
-                .and_then(|x| x.as_array())
-                .map(|x| x.into_iter().cloned().collect())
-                .unwrap_or(Vec::new()), cont)
-        })
-    }
+    /// Gets the markdown content of the article.
+    pub fn get_content(&self) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "extracts|revisions"),
+            ("explaintext", ""),
+            ("rvprop", "ids"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
 
-    /// Creates an iterator to view all langlinks of the `Page`.
-    /// This iterates over the page titles in all available languages.
-    pub fn get_langlinks(&self) -> Result<Iter<A, iter::LangLink>> {
-        Iter::new(&self)
+        match self.redirect(&q)? {
+            Some(r) => return Page::from_title(&self.wikipedia, r).get_content(),
+            None => (),
+        };
+
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    /// Returns the latitude and longitude associated to the `Page` if any.
-    pub fn get_coordinates(&self) -> Result<Option<(f64, f64)>> {
+    /// Like `get_content`, choosing how section headings are written.
+    pub fn get_content_formatted(&self, format: SectionFormat) -> Result<String> {
         let qp = self.identifier.query_param();
-        let params = vec![
-            ("prop", "coordinates"),
-            ("colimit", "max"),
+        let q = self.query(vec![
+            ("prop", "extracts"),
+            ("explaintext", ""),
+            ("exsectionformat", format.as_str()),
             ("redirects", ""),
             ("format", "json"),
             ("action", "query"),
             (&*qp.0, &*qp.1),
-        ];
-        let q = self.wikipedia.query(params.into_iter())?;
+        ].into_iter())?;
 
-        match self.redirect(&q) {
-            Some(r) => return Page::from_title(&self.wikipedia, r).get_coordinates(),
-            None => (),
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_content_formatted(format);
         }
 
-        let coord = match self.get_first_page(&q)
-                .and_then(|x| x.as_object())
-                .and_then(|x| x.get("coordinates"))
-                .and_then(|x| x.as_array())
-                .and_then(|x| x.into_iter().next())
-                .and_then(|x| x.as_object()) {
-            Some(c) => c,
-            None => return Ok(None),
-        };
-        Ok(Some((
-            coord.get("lat").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
-            coord.get("lon").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
-        )))
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    /// Fetches all sections of the article.
-    pub fn get_sections(&self) -> Result<Vec<String>> {
-        let pageid = self.get_pageid()?;
-        let params = vec![
-            ("prop", "sections"),
+    /// Gets the markdown content of the article along with the id of its
+    /// current revision, which changes every time the article is edited.
+    pub fn get_content_with_revid(&self) -> Result<(String, u64)> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "extracts|revisions"),
+            ("explaintext", ""),
+            ("rvprop", "ids"),
+            ("redirects", ""),
             ("format", "json"),
-            ("action", "parse"),
-            ("pageid", &*pageid),
-        ];
-        let q = self.wikipedia.query(params.into_iter())?;
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
 
-        Ok(q
-            .as_object()
-            .and_then(|x| x.get("parse"))
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_content_with_revid();
+        }
+
+        let page = self.get_first_page(&q)
             .and_then(|x| x.as_object())
-            .and_then(|x| x.get("sections"))
-            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?;
+        let extract = page
+            .get("extract")
+            .and_then(|x| x.as_str())
             .ok_or(Error::JSONPathError)?
-            .into_iter()
-            .filter_map(|x| x.as_object()
-                    .and_then(|x| x.get("line"))
-                    .and_then(|x| x.as_str())
-                    .map(|x| x.to_owned())
-                    )
-            .collect())
-    }
-
-    /// Fetches the content of a section.
-    pub fn get_section_content(&self, title: &str) -> Result<Option<String>> {
-        let headr = format!("== {} ==", title);
-        let content = self.get_content()?;
-        let index = match content.find(&*headr) {
-            Some(i) => headr.len() + i,
-            None => return Ok(None),
-        };
-        let end = match content[index..].find("==") {
-            Some(i) => index + i,
-            None => content.len(),
-        };
-        Ok(Some(content[index..end].to_owned()))
+            .to_owned();
+        let revid = page
+            .get("revisions")
+            .and_then(|x| x.as_array())
+            .and_then(|x| x.iter().next())
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("revid"))
+            .and_then(|x| x.as_u64())
+            .ok_or(Error::JSONPathError)?;
+        Ok((extract, revid))
     }
-}
 
-impl<'a, A: http::HttpClient> PartialEq<Page<'a, A>> for Page<'a, A> {
-    fn eq(&self, other: &Page<A>) -> bool {
-        match self.identifier {
-            TitlePageId::Title(ref t1) => match other.identifier {
-                TitlePageId::Title(ref t2) => t1 == t2,
-                TitlePageId::PageId(_) => false,
-            },
-            TitlePageId::PageId(ref p1) => match other.identifier {
-                TitlePageId::Title(_) => false,
-                TitlePageId::PageId(ref p2) => p1 == p2,
-            },
+    /// Gets the html content of the article.
+    pub fn get_html_content(&self) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let mut params = vec![
+            ("prop", "revisions"),
+            ("rvprop", "content"),
+        ];
+        if !self.wikipedia.legacy_revisions {
+            params.push(("rvslots", "main"));
         }
+        params.extend(vec![
+            ("rvlimit", "1"),
+            ("rvparse", ""),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ]);
+        let q = self.query(params.into_iter())?;
+
+        match self.redirect(&q)? {
+            Some(r) => return Page::from_title(&self.wikipedia, r).get_html_content(),
+            None => (),
+        }
+
+        Ok(self.get_first_page(&q)
+            .and_then(revision_content)
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::Wikipedia;
-    use super::http::HttpClient;
-    use super::iter;
-    use std::sync::Mutex;
+    /// Gets the wikitext source of the article.
+    pub fn get_wikitext(&self) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let mut params = vec![
+            ("prop", "revisions"),
+            ("rvprop", "content"),
+        ];
+        if !self.wikipedia.legacy_revisions {
+            params.push(("rvslots", "main"));
+        }
+        params.extend(vec![
+            ("rvlimit", "1"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ]);
+        let q = self.query(params.into_iter())?;
 
-    struct MockClient {
-        pub url: Mutex<Vec<String>>,
-        pub user_agent: Option<String>,
-        pub arguments: Mutex<Vec<Vec<(String, String)>>>,
-        pub response: Mutex<Vec<String>>,
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_wikitext();
+        }
+
+        Ok(self.get_first_page(&q)
+            .and_then(revision_content)
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
     }
 
-    impl Default for MockClient {
-        fn default() -> Self {
-            MockClient {
-                url: Mutex::new(Vec::new()),
-                user_agent: None,
-                arguments: Mutex::new(Vec::new()),
-                response: Mutex::new(Vec::new()),
+    /// Gets the named parameters of the first infobox in the `Page`, with
+    /// most of the markup removed from their values. Returns `None` if the
+    /// `Page` has no infobox.
+    pub fn get_infobox(&self) -> Result<Option<BTreeMap<String, String>>> {
+        Ok(infobox(&self.get_wikitext()?))
+    }
+
+    /// Gets the wikitext source of the article as it was in revision
+    /// `revid`.
+    pub fn get_content_by_revision(&self, revid: u64) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let rvstartid = revid.to_string();
+        let mut params = vec![
+            ("prop", "revisions"),
+            ("rvprop", "content"),
+        ];
+        if !self.wikipedia.legacy_revisions {
+            params.push(("rvslots", "main"));
+        }
+        params.extend(vec![
+            ("rvstartid", &*rvstartid),
+            ("rvlimit", "1"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ]);
+        let q = self.query(params.into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_content_by_revision(revid);
+        }
+
+        Ok(self.get_first_page(&q)
+            .and_then(revision_content)
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
+    }
+
+    /// Gets both the summary and the content of the article with a single
+    /// request, as (`summary`, `content`). The summary is the text before
+    /// the first section of the content, so it may differ slightly from the
+    /// one returned by `get_summary`, in exchange for saving a request.
+    pub fn get_extracts(&self) -> Result<(String, String)> {
+        let content = self.get_content()?;
+        let mut intro_len = 0;
+        for line in content.split_inclusive('\n') {
+            if parse_heading(line).is_some() {
+                break;
             }
+            intro_len += line.len();
         }
+        Ok((content[..intro_len].trim().to_owned(), content))
     }
 
-    impl super::http::HttpClient for MockClient {
-        fn user_agent(&mut self, user_agent: String) {
-            self.user_agent = Some(user_agent)
+    /// Gets a summary of the article.
+    pub fn get_summary(&self) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "extracts"),
+            ("explaintext", ""),
+            ("exintro", ""),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+
+        match self.redirect(&q)? {
+            Some(r) => return Page::from_title(&self.wikipedia, r).get_summary(),
+            None => (),
         }
 
-        fn get<'a, I>(&self, base_url: &str, args: I) -> Result<String, super::http::Error>
-                where I: Iterator<Item=(&'a str, &'a str)> {
-            self.url.lock().unwrap().push(base_url.to_owned());
-            self.arguments.lock().unwrap().push(args.map(|x| (x.0.to_owned(), x.1.to_owned())).collect());
-            Ok(self.response.lock().unwrap().remove(0))
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
+    }
+
+    /// Gets a summary of the article limited to a number of `sentences` or
+    /// `chars`.
+    pub fn get_summary_limited(&self, sentences: Option<u32>, chars: Option<u32>) -> Result<String> {
+        let qp = self.identifier.query_param();
+        let exsentences = sentences.map(|x| x.to_string());
+        let exchars = chars.map(|x| x.to_string());
+        let mut params = vec![
+            ("prop", "extracts"),
+            ("explaintext", ""),
+            ("exintro", ""),
+        ];
+        if let Some(ref s) = exsentences {
+            params.push(("exsentences", s));
+        }
+        if let Some(ref c) = exchars {
+            params.push(("exchars", c));
+        }
+        params.extend(vec![
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ]);
+        let q = self.query(params.into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_summary_limited(sentences, chars);
+        }
+
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("extract"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned())
+    }
+
+    /// Gets the Wikidata item id of the `Page`, for example "Q42".
+    pub fn get_wikidata_id(&self) -> Result<Option<String>> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "pageprops"),
+            ("ppprop", "wikibase_item"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_wikidata_id();
         }
+
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?
+            .get("pageprops")
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("wikibase_item"))
+            .and_then(|x| x.as_str())
+            .map(|x| x.to_owned()))
     }
 
-    #[test]
-    fn base_url() {
-        let mut wikipedia = Wikipedia::<MockClient>::default();
-        assert_eq!(wikipedia.base_url(), "https://en.wikipedia.org/w/api.php");
-        wikipedia.language = "es".to_owned();
-        assert_eq!(wikipedia.base_url(), "https://es.wikipedia.org/w/api.php");
+    /// Gets the url of the `Page`'s main image thumbnail, `size` pixels wide
+    /// at most, or `None` if the page has no lead image.
+    pub fn get_main_image(&self, size: u32) -> Result<Option<String>> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "pageimages"),
+            ("piprop", "thumbnail"),
+            ("pithumbsize", &*size.to_string()),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
 
-        wikipedia.set_base_url("https://hello.{language}.world/");
-        assert_eq!(wikipedia.base_url(), "https://hello.es.world/");
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_main_image(size);
+        }
 
-        wikipedia.set_base_url("https://hello.world/");
-        assert_eq!(wikipedia.base_url(), "https://hello.world/");
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?
+            .get("thumbnail")
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("source"))
+            .and_then(|x| x.as_str())
+            .map(|x| x.to_owned()))
     }
 
-    #[test]
-    fn user_agent() {
-        let mut wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{}".to_owned());
-        wikipedia.search("hello world").unwrap_err();
-        assert_eq!(&*wikipedia.client.user_agent.unwrap(), "wikipedia (https://github.com/seppo0010/wikipedia-rs)");
+    /// Gets the title, short description, summary and main image thumbnail
+    /// of the `Page` in a single request, as needed to show a preview of it.
+    pub fn get_preview(&self) -> Result<Preview> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "extracts|pageimages|pageprops"),
+            ("explaintext", ""),
+            ("exintro", ""),
+            ("piprop", "thumbnail"),
+            ("pithumbsize", &*PREVIEW_THUMBNAIL_SIZE.to_string()),
+            ("ppprop", "wikibase-shortdesc"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
 
-        let mut client = MockClient::default();
-        client.user_agent("hello world".to_owned());
-        client.response.lock().unwrap().push("{}".to_owned());
-        wikipedia.client = client;
-        wikipedia.search("hello world").unwrap_err();
-        assert_eq!(&*wikipedia.client.user_agent.unwrap(), "hello world");
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_preview();
+        }
+
+        let page = self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?;
+        let string = |k: &str| page.get(k).and_then(|x| x.as_str()).map(|x| x.to_owned());
+        Ok(Preview {
+            title: string("title").ok_or(Error::JSONPathError)?,
+            description: page.get("pageprops")
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("wikibase-shortdesc"))
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_owned()),
+            extract: string("extract").ok_or(Error::JSONPathError)?,
+            thumbnail: page.get("thumbnail")
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("source"))
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_owned()),
+        })
+    }
+
+    /// Receive a json object and extracts any `continue` parameters to be
+    /// used when browsing following pages.
+    fn parse_cont(&self, q: &serde_json::Value) -> Result<Continuation> {
+        let cont = match q
+            .as_object()
+            .and_then(|x| x.get("continue"))
+            .and_then(|x| x.as_object()) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+        let mut cont_v = vec![];
+        for (k, v) in cont.into_iter() {
+            let value = match *v {
+                serde_json::Value::Null => "".to_owned(),
+                serde_json::Value::Bool(b) => if b { "1" } else { "0" }.to_owned(),
+                serde_json::Value::Number(ref f) => format!("{}", f),
+                serde_json::Value::String(ref s) => s.clone(),
+                _ => return Err(Error::JSONPathError),
+            };
+            cont_v.push((k.clone(), value));
+        }
+        Ok(Some(cont_v))
+    }
+
+    fn request_images(&self, cont: &Continuation) -> Result<Batch> {
+        cont!(self, cont,
+            ("generator", "images"),
+            ("gimlimit", &*self.wikipedia.images_results),
+            ("prop", "imageinfo"),
+            ("iiprop", "url|size|mime")
+        )
+    }
+
+    /// Creates an iterator to view all images in the `Page`.
+    pub fn get_images(&self) -> Result<Iter<A, iter::Image>> {
+        Iter::new(&self)
+    }
+
+    /// Like `get_all_links`, for images.
+    pub fn get_all_images(&self) -> Result<Vec<iter::Image>> {
+        self.get_images()?.try_iter().collect()
+    }
+
+    /// Like `get_images`, but only returns the files with one of the given
+    /// `extensions`, for example `&["jpg", "jpeg", "png"]`. The comparison
+    /// ignores case.
+    pub fn get_images_filtered(&self, extensions: &[&str]) -> Result<impl Iterator<Item = iter::Image> + 'a> {
+        let extensions: Vec<String> = extensions
+            .iter()
+            .map(|x| x.trim_start_matches('.').to_lowercase())
+            .collect();
+        Ok(Iter::new(self)?.filter(move |i: &iter::Image| {
+            let title = i.title.to_lowercase();
+            match title.rsplit_once('.') {
+                Some((_, extension)) => extensions.iter().any(|x| x == extension),
+                None => false,
+            }
+        }))
+    }
+
+    /// Counts the images in the `Page`. The api does not report totals, so
+    /// all of them are requested, `images_results` at a time.
+    pub fn count_images(&self) -> Result<usize> {
+        self.get_images()?.try_iter().try_fold(0, |n, x| x.map(|_| n + 1))
+    }
+
+    fn request_extlinks(&self, cont: &Continuation) -> Result<Batch> {
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", "extlinks"),
+            ("ellimit", &*self.wikipedia.links_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get("extlinks"))
+                .and_then(|x| x.as_array())
+                .map(|x| x.into_iter().cloned().collect())
+                .unwrap_or(Vec::new()), cont)
+        })
+    }
+
+    /// Creates an iterator to view all references (external links) in the `Page`.
+    pub fn get_references(&self) -> Result<Iter<A, iter::Reference>> {
+        Iter::new(&self)
+    }
+
+    /// Like `get_all_links`, for references.
+    pub fn get_all_references(&self) -> Result<Vec<iter::Reference>> {
+        self.get_references()?.try_iter().collect()
+    }
+
+    fn request_links(&self, cont: &Continuation) -> Result<Batch> {
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", "links"),
+            ("plnamespace", "0"),
+            ("pllimit", &*self.wikipedia.links_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get("links"))
+                .and_then(|x| x.as_array())
+                .map(|x| x.into_iter().cloned().collect())
+                .unwrap_or(Vec::new()), cont)
+        })
+    }
+
+    /// Creates an iterator to view all internal links in the `Page`.
+    pub fn get_links(&self) -> Result<Iter<A, iter::Link>> {
+        Iter::new(&self)
+    }
+
+    /// Like `get_links`, but returns all of them at once. Unlike collecting
+    /// the iterator, it fails if any of the requests does, instead of
+    /// returning the links fetched until then.
+    pub fn get_all_links(&self) -> Result<Vec<iter::Link>> {
+        self.get_links()?.try_iter().collect()
+    }
+
+    /// Counts the internal links in the `Page`. Like `count_images`, all of
+    /// them are requested, `links_results` at a time.
+    pub fn count_links(&self) -> Result<usize> {
+        self.get_links()?.try_iter().try_fold(0, |n, x| x.map(|_| n + 1))
+    }
+
+    /// Returns the localized prefix of category titles, for example
+    /// "Category:".
+    fn category_prefix(&self) -> Result<String> {
+        Ok(match self.wikipedia.get_namespaces()?.get(&CATEGORY_NAMESPACE) {
+            Some(name) => format!("{}:", name),
+            None => "Category:".to_owned(),
+        })
+    }
+
+    fn request_categories(&self, cont: &Continuation) -> Result<Batch> {
+        let prefix = self.category_prefix()?;
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", "categories"),
+            ("clprop", "hidden|sortkey"),
+            ("cllimit", &*self.wikipedia.categories_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get("categories"))
+                .and_then(|x| x.as_array())
+                .map(|x| x.iter().map(|c| {
+                    let mut c = c.clone();
+                    let title = c.get("title")
+                        .and_then(|x| x.as_str())
+                        .and_then(|x| x.strip_prefix(&*prefix))
+                        .map(|x| x.to_owned());
+                    if let Some(title) = title {
+                        c["title"] = title.into();
+                    }
+                    c
+                }).collect())
+                .unwrap_or(Vec::new()), cont)
+        })
+    }
+
+    /// Creates an iterator to view all categories of the `Page`.
+    pub fn get_categories(&self) -> Result<Iter<A, iter::Category>> {
+        Iter::new(&self)
+    }
+
+    /// Like `get_all_links`, for categories.
+    pub fn get_all_categories(&self) -> Result<Vec<iter::Category>> {
+        self.get_categories()?.try_iter().collect()
+    }
+
+    /// Like `get_all_categories`, but also fills the `pageid` of each
+    /// category. The categories module does not include them, so this
+    /// sends an extra request for every `MAX_TITLES` categories.
+    pub fn get_categories_with_ids(&self) -> Result<Vec<iter::Category>> {
+        let mut categories = self.get_all_categories()?;
+        let prefix = self.category_prefix()?;
+        let titles: Vec<String> = categories.iter()
+            .map(|c| format!("{}{}", prefix, c.title))
+            .collect();
+        let pageids = self.wikipedia.request_pageids(&titles)?;
+        for (category, title) in categories.iter_mut().zip(titles.iter()) {
+            category.pageid = pageids.get(title).cloned();
+        }
+        Ok(categories)
+    }
+
+    /// Like `get_categories`, but skips hidden categories, which are
+    /// usually used for maintenance.
+    pub fn get_visible_categories(&self) -> Result<impl Iterator<Item = iter::Category> + 'a> {
+        Ok(Iter::new(self)?.filter(|c: &iter::Category| !c.hidden))
+    }
+
+    fn request_langlinks(&self, cont: &Continuation) -> Result<Batch> {
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", "langlinks"),
+            ("llprop", "url"),
+            ("lllimit", &*self.wikipedia.links_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get("langlinks"))
+                .and_then(|x| x.as_array())
+                .map(|x| x.into_iter().cloned().collect())
+                .unwrap_or(Vec::new()), cont)
+        })
+    }
+
+    /// Gets the title of the `Page` in the language `lang`, or `None` if
+    /// there is no langlink to it.
+    pub fn get_langlink(&self, lang: &str) -> Result<Option<String>> {
+        let qp = self.identifier.query_param();
+        let q = self.query(vec![
+            ("prop", "langlinks"),
+            ("lllang", lang),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ].into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_langlink(lang);
+        }
+
+        Ok(self.get_first_page(&q)
+            .and_then(|x| x.as_object())
+            .ok_or(Error::JSONPathError)?
+            .get("langlinks")
+            .and_then(|x| x.as_array())
+            .and_then(|x| x.iter().next())
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("*"))
+            .and_then(|x| x.as_str())
+            .map(|x| x.to_owned()))
+    }
+
+    /// Gets the `Page` this one links to in the language `lang`, bound to a
+    /// copy of the `Wikipedia` using that language. Returns `None` if there
+    /// is no langlink to it.
+    pub fn in_language(&self, lang: &str) -> Result<Option<Page<'a, A>>>
+    where
+        A: Clone,
+    {
+        let title = match self.get_langlink(lang)? {
+            Some(title) => title,
+            None => return Ok(None),
+        };
+        let wikipedia = (*self.wikipedia).clone().with_language(lang);
+        Ok(Some(Page {
+            wikipedia: WikipediaRef::Owned(Arc::new(wikipedia)),
+            identifier: TitlePageId::Title(title),
+            follow_redirects: self.follow_redirects,
+        }))
+    }
+
+    /// Creates an iterator to view all langlinks of the `Page`.
+    /// This iterates over the page titles in all available languages.
+    pub fn get_langlinks(&self) -> Result<Iter<A, iter::LangLink>> {
+        Iter::new(&self)
+    }
+
+    /// Like `get_all_links`, for langlinks.
+    pub fn get_all_langlinks(&self) -> Result<Vec<iter::LangLink>> {
+        self.get_langlinks()?.try_iter().collect()
+    }
+
+    /// Requests a page of a `prop` module whose results are returned in
+    /// the `array_key` field of the page, such as `templates`.
+    fn request_prop(&self, cont: &Continuation, prop: &str, limit_param: &str, array_key: &str) ->
+            Result<Batch> {
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", prop),
+            (limit_param, &*self.wikipedia.links_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get(array_key))
+                .and_then(|x| x.as_array())
+                .cloned()
+                .unwrap_or_default(), cont)
+        })
+    }
+
+    /// Creates an iterator over the raw results of any `prop` module, for
+    /// modules without a dedicated method. `limit_param` is the name of the
+    /// module's limit parameter, which is set to `links_results`, and
+    /// `array_key` the field holding the results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// extern crate wikipedia;
+    ///
+    /// let wiki = wikipedia::Wikipedia::<wikipedia::http::default::Client>::default();
+    /// let page = wiki.page_from_title("Template:Main".to_owned());
+    /// let mut transcluded = page.query_prop_iter("transcludedin", "tilimit", "transcludedin").unwrap();
+    /// assert!(transcluded.next().is_some());
+    /// ```
+    pub fn query_prop_iter(&self, prop: &str, limit_param: &str, array_key: &str) -> Result<iter::PropIter<'a, A>> {
+        iter::PropIter::new(self, prop, limit_param, array_key)
+    }
+
+    fn request_templates(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_prop(cont, "templates", "tllimit", "templates")
+    }
+
+    /// Creates an iterator to view all templates used in the `Page`.
+    pub fn get_templates(&self) -> Result<Iter<A, iter::Template>> {
+        Iter::new(self)
+    }
+
+    /// Like `get_all_links`, for templates.
+    pub fn get_all_templates(&self) -> Result<Vec<iter::Template>> {
+        self.get_templates()?.try_iter().collect()
+    }
+
+    fn request_iwlinks(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_prop(cont, "iwlinks", "iwlimit", "iwlinks")
+    }
+
+    /// Creates an iterator to view all links from the `Page` to other wikis,
+    /// such as Wiktionary. Links to the same article in other languages are
+    /// returned by `get_langlinks` instead.
+    pub fn get_iwlinks(&self) -> Result<Iter<'a, A, iter::IwLink>> {
+        Iter::new(self)
+    }
+
+    fn request_revisions(&self, cont: &Continuation) -> Result<Batch> {
+        let a:Result<(Vec<serde_json::Value>, _)> = cont!(self, cont,
+            ("prop", "revisions"),
+            ("rvprop", "ids|timestamp|user|comment|size"),
+            ("rvlimit", &*self.wikipedia.links_results)
+        );
+        a.map(|(pages, cont)| {
+            let page = match pages.into_iter().next() {
+                Some(p) => p,
+                None => return (Vec::new(), None),
+            };
+            (page
+                .as_object()
+                .and_then(|x| x.get("revisions"))
+                .and_then(|x| x.as_array())
+                .cloned()
+                .unwrap_or_default(), cont)
+        })
+    }
+
+    /// Creates an iterator to view the revision history of the `Page`,
+    /// newest first.
+    pub fn get_revisions(&self) -> Result<Iter<'a, A, iter::Revision>> {
+        Iter::new(self)
+    }
+
+    fn request_contributors(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_prop(cont, "contributors", "pclimit", "contributors")
+    }
+
+    /// Creates an iterator to view the registered users that edited the
+    /// `Page`.
+    pub fn get_contributors(&self) -> Result<Iter<'a, A, iter::Contributor>> {
+        Iter::new(self)
+    }
+
+    fn request_redirects(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_prop(cont, "redirects", "rdlimit", "redirects")
+    }
+
+    /// Creates an iterator to view the pages that redirect to the `Page`,
+    /// such as alternative names and common misspellings.
+    pub fn get_redirects(&self) -> Result<Iter<'a, A, iter::Redirect>> {
+        Iter::new(self)
+    }
+
+    /// Requests a page of a `list` module that takes this `Page` as
+    /// argument, such as `categorymembers` or `backlinks`. `prefix` is the
+    /// module's parameter prefix, for example "cm" or "bl".
+    fn request_list(&self, cont: &Continuation, list: &str, prefix: &str, limit: &str) ->
+            Result<Batch> {
+        let (key, value) = match self.identifier {
+            TitlePageId::Title(ref s) => (format!("{}title", prefix), s),
+            TitlePageId::PageId(ref s) => (format!("{}pageid", prefix), s),
+        };
+        let limit_key = format!("{}limit", prefix);
+        let mut params = vec![
+            ("list", list),
+            (&*key, &**value),
+            (&*limit_key, limit),
+            ("format", "json"),
+            ("action", "query"),
+        ];
+        match *cont {
+            Some(ref v) => {
+                for x in v.iter() { params.push((&*x.0, &*x.1)); }
+            },
+            None => params.push(("continue", "")),
+        }
+        let q = self.query(params.into_iter())?;
+
+        let results = q
+            .as_object()
+            .and_then(|x| x.get("query"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get(list))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?;
+
+        Ok((results.clone(), self.parse_cont(&q)?))
+    }
+
+    fn request_category_members(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_list(cont, "categorymembers", "cm", &self.wikipedia.categories_results)
+    }
+
+    fn request_backlinks(&self, cont: &Continuation) -> Result<Batch> {
+        self.request_list(cont, "backlinks", "bl", &self.wikipedia.links_results)
+    }
+
+    /// Creates an iterator to view all pages linking to the `Page`.
+    pub fn get_backlinks(&self) -> Result<Iter<'a, A, iter::Backlink>> {
+        Iter::new(self)
+    }
+
+    /// Like `get_all_links`, for backlinks.
+    pub fn get_all_backlinks(&self) -> Result<Vec<iter::Backlink>> {
+        self.get_backlinks()?.try_iter().collect()
+    }
+
+    /// Returns the latitude and longitude associated to the `Page` if any.
+    pub fn get_coordinates(&self) -> Result<Option<(f64, f64)>> {
+        let qp = self.identifier.query_param();
+        let params = vec![
+            ("prop", "coordinates"),
+            ("colimit", "max"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ];
+        let q = self.query(params.into_iter())?;
+
+        match self.redirect(&q)? {
+            Some(r) => return Page::from_title(&self.wikipedia, r).get_coordinates(),
+            None => (),
+        }
+
+        let coord = match self.get_first_page(&q)
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("coordinates"))
+                .and_then(|x| x.as_array())
+                .and_then(|x| x.into_iter().next())
+                .and_then(|x| x.as_object()) {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+        Ok(Some((
+            coord.get("lat").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
+            coord.get("lon").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
+        )))
+    }
+
+    /// Like `Wikipedia::geosearch`, but searches around the coordinates of
+    /// the `Page` instead of a given point. The `Page` itself is usually the
+    /// first result.
+    pub fn geosearch_nearby(&self, radius: u16) -> Result<Vec<String>> {
+        if radius < 10 || radius > 10000 {
+            return Err(Error::InvalidParameter("radius".to_string()))
+        }
+        let (key, value) = match self.identifier {
+            TitlePageId::Title(ref s) => ("gspage", s),
+            TitlePageId::PageId(ref s) => ("gspageid", s),
+        };
+        let results = &*format!("{}", self.wikipedia.search_results);
+        let data = self.wikipedia.query(vec![
+            ("list", "geosearch"),
+            ("gsradius", &*format!("{}", radius)),
+            (key, &**value),
+            ("gslimit", results),
+            ("format", "json"),
+            ("action", "query"),
+        ].into_iter())?;
+        Ok(results!(data, "geosearch"))
+    }
+
+    /// Returns all the points associated to the `Page`, including the
+    /// secondary ones and those not on Earth.
+    pub fn get_all_coordinates(&self) -> Result<Vec<Coordinate>> {
+        let qp = self.identifier.query_param();
+        let params = vec![
+            ("prop", "coordinates"),
+            ("coprop", "type|dim|globe"),
+            ("coprimary", "all"),
+            ("colimit", "max"),
+            ("redirects", ""),
+            ("format", "json"),
+            ("action", "query"),
+            (&*qp.0, &*qp.1),
+        ];
+        let q = self.query(params.into_iter())?;
+
+        if let Some(r) = self.redirect(&q)? {
+            return Page::from_title(&self.wikipedia, r).get_all_coordinates();
+        }
+
+        let coords = match self.get_first_page(&q)
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("coordinates"))
+                .and_then(|x| x.as_array()) {
+            Some(c) => c,
+            None => return Ok(Vec::new()),
+        };
+        coords.iter().map(|c| {
+            let c = c.as_object().ok_or(Error::JSONPathError)?;
+            Ok(Coordinate {
+                lat: c.get("lat").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
+                lon: c.get("lon").and_then(|x| x.as_f64()).ok_or(Error::JSONPathError)?,
+                globe: c.get("globe").and_then(|x| x.as_str()).unwrap_or("earth").to_owned(),
+                primary: c.contains_key("primary"),
+                kind: c.get("type").and_then(|x| x.as_str()).map(|x| x.to_owned()),
+                dim: c.get("dim").and_then(|x| x.as_u64()),
+            })
+        }).collect()
+    }
+
+    /// Returns the number of views of the `Page` for each of the last `days`
+    /// days, as `(date, views)` where the date is formatted as "YYYYMMDD".
+    /// The current day is not included, since its data is incomplete.
+    pub fn get_pageviews(&self, days: u32) -> Result<Vec<(String, u64)>> {
+        if days == 0 {
+            return Ok(Vec::new());
+        }
+        let today = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|x| (x.as_secs() / 86400) as i64)
+            .unwrap_or(0);
+        self.request_pageviews(&format_days(today - days as i64), &format_days(today - 1))
+    }
+
+    /// Requests the daily pageviews between `start` and `end`, both
+    /// included and formatted as "YYYYMMDD".
+    fn request_pageviews(&self, start: &str, end: &str) -> Result<Vec<(String, u64)>> {
+        let title = self.resolved_title()?.replace(' ', "_");
+        let url = format!(
+            "https://wikimedia.org/api/rest_v1/metrics/pageviews/per-article/{}/all-access/user/{}/daily/{}00/{}00",
+            self.wikipedia.host(), encode_path_segment(&title), start, end);
+        let q = self.wikipedia.fetch_json(&url)?;
+        q.as_object()
+            .and_then(|x| x.get("items"))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?
+            .iter()
+            .map(|i| {
+                let i = i.as_object().ok_or(Error::JSONPathError)?;
+                let timestamp = i.get("timestamp").and_then(|x| x.as_str()).ok_or(Error::JSONPathError)?;
+                Ok((
+                    timestamp.chars().take(8).collect(),
+                    i.get("views").and_then(|x| x.as_u64()).ok_or(Error::JSONPathError)?,
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the summary of the `Page` from the rest api, which includes
+    /// its description and thumbnail.
+    pub fn get_rest_summary(&self) -> Result<RestSummary> {
+        let title = self.resolved_title()?.replace(' ', "_");
+        let url = format!("{}/page/summary/{}", self.wikipedia.rest_base_url(), encode_path_segment(&title));
+        let q = self.wikipedia.fetch_json(&url)?;
+        let q = q.as_object().ok_or(Error::JSONPathError)?;
+        let string = |k: &str| q.get(k).and_then(|x| x.as_str()).map(|x| x.to_owned());
+        Ok(RestSummary {
+            title: q.get("titles")
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("normalized"))
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_owned())
+                .or_else(|| string("title").map(|x| x.replace('_', " ")))
+                .ok_or(Error::JSONPathError)?,
+            description: string("description"),
+            extract: string("extract").ok_or(Error::JSONPathError)?,
+            thumbnail_url: q.get("thumbnail")
+                .and_then(|x| x.as_object())
+                .and_then(|x| x.get("source"))
+                .and_then(|x| x.as_str())
+                .map(|x| x.to_owned()),
+            content_urls: q.get("content_urls")
+                .and_then(|x| x.as_object())
+                .map(|x| x.iter().filter_map(|(platform, urls)| {
+                    let url = urls.as_object()?.get("page")?.as_str()?;
+                    Some((platform.clone(), url.to_owned()))
+                }).collect())
+                .unwrap_or_default(),
+        })
+    }
+
+    fn request_sections(&self) -> Result<Vec<serde_json::Value>> {
+        let pageid = self.get_pageid()?;
+        let params = vec![
+            ("prop", "sections"),
+            ("format", "json"),
+            ("action", "parse"),
+            ("pageid", &*pageid),
+        ];
+        let q = self.query(params.into_iter())?;
+
+        Ok(q
+            .as_object()
+            .and_then(|x| x.get("parse"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("sections"))
+            .and_then(|x| x.as_array())
+            .ok_or(Error::JSONPathError)?
+            .clone())
+    }
+
+    /// Fetches all sections of the article.
+    pub fn get_sections(&self) -> Result<Vec<String>> {
+        Ok(self.request_sections()?
+            .iter()
+            .filter_map(|x| x.as_object()
+                    .and_then(|x| x.get("line"))
+                    .and_then(|x| x.as_str())
+                    .map(|x| x.to_owned())
+                    )
+            .collect())
+    }
+
+    /// Fetches all sections of the article, including their heading level,
+    /// number and anchor.
+    pub fn get_sections_detailed(&self) -> Result<Vec<Section>> {
+        Ok(self.request_sections()?
+            .iter()
+            .filter_map(|x| {
+                let o = x.as_object()?;
+                let string = |key| o.get(key).and_then(|x| x.as_str()).map(|x| x.to_owned());
+                Some(Section {
+                    line: string("line")?,
+                    level: string("level")?.parse().ok()?,
+                    number: string("number")?,
+                    anchor: string("anchor")?,
+                    index: string("index")?,
+                })
+            })
+            .collect())
+    }
+
+    /// Fetches the content of the section at `index` (see `Section::index`)
+    /// using the parse API. Returns its html if `html` is true, or its
+    /// wikitext otherwise. Returns `None` if there is no such section.
+    pub fn get_section_content_by_index(&self, index: u32, html: bool) -> Result<Option<String>> {
+        let pageid = self.get_pageid()?;
+        let prop = if html { "text" } else { "wikitext" };
+        let section = index.to_string();
+        let params = vec![
+            ("prop", prop),
+            ("section", &*section),
+            ("format", "json"),
+            ("action", "parse"),
+            ("pageid", &*pageid),
+        ];
+        let q = match self.query(params.into_iter()) {
+            Ok(q) => q,
+            Err(Error::ApiError { ref code, .. }) if code == "nosuchsection" => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        Ok(Some(q
+            .as_object()
+            .and_then(|x| x.get("parse"))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get(prop))
+            .and_then(|x| x.as_object())
+            .and_then(|x| x.get("*"))
+            .and_then(|x| x.as_str())
+            .ok_or(Error::JSONPathError)?
+            .to_owned()))
+    }
+
+    /// Fetches the content of a section, including its subsections.
+    pub fn get_section_content(&self, title: &str) -> Result<Option<String>> {
+        let content = self.get_content()?;
+        Ok(section_content(&content, title).map(|x| x.to_owned()))
+    }
+
+    /// Fetches the content of every section, as (`heading`, `body`) in the
+    /// order they appear. Like in `get_section_content`, the body of a
+    /// section includes its subsections. The content is requested once.
+    pub fn get_all_sections(&self) -> Result<Vec<(String, String)>> {
+        let content = self.get_content()?;
+        Ok(all_sections(&content))
+    }
+}
+
+/// If `line` is a section heading such as `== Title ==` returns its level
+/// and title.
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let line = line.trim();
+    let leading = line.len() - line.trim_start_matches('=').len();
+    let trailing = line.len() - line.trim_end_matches('=').len();
+    let level = leading.min(trailing);
+    if level < 2 || line.len() <= level * 2 {
+        return None;
+    }
+    Some((level, line[level..line.len() - level].trim()))
+}
+
+/// Finds the body of the section named `title` in `content`. The body ends
+/// at the next heading of the same or a higher level, so subsections are
+/// included.
+fn section_content<'c>(content: &'c str, title: &str) -> Option<&'c str> {
+    let mut offset = 0;
+    let mut start = None;
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        let (level, heading) = match parse_heading(line) {
+            Some(h) => h,
+            None => continue,
+        };
+        match start {
+            None => if heading == title {
+                start = Some((level, offset));
+            },
+            Some((start_level, start_offset)) => if level <= start_level {
+                return Some(&content[start_offset..line_start]);
+            },
+        }
+    }
+    start.map(|(_, start_offset)| &content[start_offset..])
+}
+
+/// Splits `content` in the body of each of its sections, as returned by
+/// `section_content`.
+fn all_sections(content: &str) -> Vec<(String, String)> {
+    let mut headings = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let line_start = offset;
+        offset += line.len();
+        if let Some((level, heading)) = parse_heading(line) {
+            headings.push((level, heading, line_start, offset));
+        }
+    }
+    headings.iter().enumerate().map(|(i, &(level, heading, _, start))| {
+        let end = headings[i + 1..].iter()
+            .find(|&&(l, _, _, _)| l <= level)
+            .map(|&(_, _, line_start, _)| line_start)
+            .unwrap_or(content.len());
+        (heading.to_owned(), content[start..end].to_owned())
+    }).collect()
+}
+
+/// Finds the first `{{Infobox ...}}` template in `wikitext` and returns its
+/// named parameters with their values stripped of most markup. Empty
+/// parameters are skipped.
+fn infobox(wikitext: &str) -> Option<BTreeMap<String, String>> {
+    let start = wikitext.match_indices("{{").map(|(i, _)| i + 2).find(|&i| {
+        wikitext[i..].trim_start().get(..7).map(|x| x.eq_ignore_ascii_case("infobox")).unwrap_or(false)
+    })?;
+
+    // split the parameters on the `|` that are not inside nested templates
+    // or links
+    let bytes = wikitext.as_bytes();
+    let mut templates = 0;
+    let mut links = 0;
+    let mut parts = Vec::new();
+    let mut part_start = start;
+    let mut i = start;
+    loop {
+        if i >= bytes.len() {
+            return None;
+        }
+        match &bytes[i..(i + 2).min(bytes.len())] {
+            b"{{" => { templates += 1; i += 2; continue; },
+            b"}}" if templates == 0 => {
+                parts.push(&wikitext[part_start..i]);
+                break;
+            },
+            b"}}" => { templates -= 1; i += 2; continue; },
+            b"[[" => { links += 1; i += 2; continue; },
+            b"]]" if links > 0 => { links -= 1; i += 2; continue; },
+            _ => (),
+        }
+        if bytes[i] == b'|' && templates == 0 && links == 0 {
+            parts.push(&wikitext[part_start..i]);
+            part_start = i + 1;
+        }
+        i += 1;
+    }
+
+    Some(parts.into_iter().skip(1).filter_map(|part| {
+        let (key, value) = part.split_once('=')?;
+        let key = key.trim();
+        let value = strip_markup(value);
+        if key.is_empty() || value.is_empty() {
+            None
+        } else {
+            Some((key.to_owned(), value))
+        }
+    }).collect())
+}
+
+/// Removes comments, references and formatting from a piece of wikitext,
+/// and replaces links with their label.
+fn strip_markup(value: &str) -> String {
+    let mut value = value.to_owned();
+    while let Some(start) = value.find("<!--") {
+        let end = value[start..].find("-->").map(|x| start + x + 3).unwrap_or(value.len());
+        value.replace_range(start..end, "");
+    }
+    while let Some(start) = value.find("<ref") {
+        let tag_end = match value[start..].find('>') {
+            Some(x) => start + x + 1,
+            None => break,
+        };
+        let end = if value[..tag_end].ends_with("/>") {
+            tag_end
+        } else {
+            value[tag_end..].find("</ref>").map(|x| tag_end + x + 6).unwrap_or(value.len())
+        };
+        value.replace_range(start..end, "");
+    }
+    while let Some(start) = value.find("[[") {
+        let end = match value[start..].find("]]") {
+            Some(x) => start + x,
+            None => break,
+        };
+        let label = value[start + 2..end].rsplit('|').next().unwrap_or("").to_owned();
+        value.replace_range(start..end + 2, &label);
+    }
+    value.replace("'''", "").replace("''", "").trim().to_owned()
+}
+
+impl<'a, A: http::HttpClient> PartialEq<Page<'a, A>> for Page<'a, A> {
+    fn eq(&self, other: &Page<A>) -> bool {
+        match self.identifier {
+            TitlePageId::Title(ref t1) => match other.identifier {
+                TitlePageId::Title(ref t2) => t1 == t2,
+                TitlePageId::PageId(_) => false,
+            },
+            TitlePageId::PageId(ref p1) => match other.identifier {
+                TitlePageId::Title(_) => false,
+                TitlePageId::PageId(ref p2) => p1 == p2,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Wikipedia, Coordinate, GeoResult, SearchResult, PageInfo, Preview, Protection, ResolvedPage, Section, SectionFormat};
+    use super::http::HttpClient;
+    use super::iter;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    // clones share their state, so a test can see the requests made by a
+    // cloned `Wikipedia`
+    #[derive(Clone)]
+    struct MockClient {
+        pub url: Arc<Mutex<Vec<String>>>,
+        pub method: Arc<Mutex<Vec<String>>>,
+        pub user_agent: Option<String>,
+        pub arguments: Arc<Mutex<Vec<Vec<(String, String)>>>>,
+        pub response: Arc<Mutex<Vec<String>>>,
+        pub sleeps: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    impl Default for MockClient {
+        fn default() -> Self {
+            MockClient {
+                url: Arc::new(Mutex::new(Vec::new())),
+                method: Arc::new(Mutex::new(Vec::new())),
+                user_agent: None,
+                arguments: Arc::new(Mutex::new(Vec::new())),
+                response: Arc::new(Mutex::new(Vec::new())),
+                sleeps: Arc::new(Mutex::new(Vec::new())),
+            }
+        }
+    }
+
+    impl super::http::HttpClient for MockClient {
+        fn user_agent(&mut self, user_agent: String) {
+            self.user_agent = Some(user_agent)
+        }
+
+        fn get<'a, I>(&self, base_url: &str, args: I) -> Result<String, super::http::Error>
+                where I: Iterator<Item=(&'a str, &'a str)> {
+            self.url.lock().unwrap().push(base_url.to_owned());
+            self.method.lock().unwrap().push("GET".to_owned());
+            self.arguments.lock().unwrap().push(args.map(|x| (x.0.to_owned(), x.1.to_owned())).collect());
+            Ok(self.response.lock().unwrap().remove(0))
+        }
+
+        fn post<'a, I>(&self, base_url: &str, args: I) -> Result<String, super::http::Error>
+                where I: Iterator<Item=(&'a str, &'a str)> {
+            self.url.lock().unwrap().push(base_url.to_owned());
+            self.method.lock().unwrap().push("POST".to_owned());
+            self.arguments.lock().unwrap().push(args.map(|x| (x.0.to_owned(), x.1.to_owned())).collect());
+            Ok(self.response.lock().unwrap().remove(0))
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.sleeps.lock().unwrap().push(duration);
+        }
+    }
+
+    #[test]
+    fn base_url() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        assert_eq!(wikipedia.base_url(), "https://en.wikipedia.org/w/api.php");
+        wikipedia.language = "es".to_owned();
+        assert_eq!(wikipedia.base_url(), "https://es.wikipedia.org/w/api.php");
+
+        wikipedia.set_base_url("https://hello.{language}.world/");
+        assert_eq!(wikipedia.base_url(), "https://hello.es.world/");
+
+        wikipedia.set_base_url("https://hello.world/");
+        assert_eq!(wikipedia.base_url(), "https://hello.world/");
+    }
+
+    #[test]
+    fn with_options() {
+        let wikipedia = Wikipedia::<MockClient>::default()
+            .with_language("es")
+            .with_search_results(25)
+            .with_images_results("5")
+            .with_links_results("6")
+            .with_categories_results("7");
+        assert_eq!(wikipedia.base_url(), "https://es.wikipedia.org/w/api.php");
+        assert_eq!(wikipedia.search_results, 25);
+        assert_eq!(wikipedia.images_results, "5");
+        assert_eq!(wikipedia.links_results, "6");
+        assert_eq!(wikipedia.categories_results, "7");
+
+        let wikipedia = wikipedia.with_base_url("https://hello.{language}.world/");
+        assert_eq!(wikipedia.base_url(), "https://hello.es.world/");
+    }
+
+    #[test]
+    fn std_error() {
+        let error: Box<dyn std::error::Error + Send + Sync> = super::Error::InvalidParameter("radius".to_owned()).into();
+        assert_eq!(error.to_string(), "Invalid Parameter: radius");
+    }
+
+    #[test]
+    fn api_error() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"ratelimited\",\"info\":\"Slow down\"}}".to_owned());
+        match wikipedia.search("hello world").unwrap_err() {
+            super::Error::ApiError { code, info } => {
+                assert_eq!(code, "ratelimited");
+                assert_eq!(info, "Slow down");
+            },
+            e => panic!("unexpected error {:?}", e),
+        }
+    }
+
+    #[test]
+    fn last_warnings() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"warnings\":{\"extracts\":{\"*\":\"exlimit was too large\"}},\"query\":{\"search\":[]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[]}}".to_owned());
+        wikipedia.search("hello").unwrap();
+        assert_eq!(
+                wikipedia.last_warnings(),
+                vec![("extracts".to_owned(), "exlimit was too large".to_owned())]);
+        wikipedia.search("hello").unwrap();
+        assert_eq!(wikipedia.last_warnings(), vec![]);
+    }
+
+    #[test]
+    fn maxlag_retry() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.maxlag = Some(5);
+        wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"maxlag\",\"info\":\"Waiting for a database server\"}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"maxlag\",\"info\":\"Waiting for 10.64.32.21: 7 seconds lagged.\",\"host\":\"10.64.32.21\",\"lag\":7,\"type\":\"db\"}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"hello\"}]}}".to_owned());
+        assert_eq!(wikipedia.search("hello world").unwrap(), vec!["hello".to_owned()]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 3);
+        assert_eq!(*wikipedia.client.sleeps.lock().unwrap(),
+                vec![Duration::from_secs(super::MAXLAG_DELAY), Duration::from_secs(7)]);
+    }
+
+    #[test]
+    fn maxlag_retry_exhausted() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.maxlag = Some(5);
+        for _ in 0..4 {
+            wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"maxlag\",\"info\":\"Waiting for a database server\",\"lag\":1.5}}".to_owned());
+        }
+        assert_eq!(wikipedia.search("hello world"), Err(super::Error::ApiError {
+            code: "maxlag".to_owned(),
+            info: "Waiting for a database server".to_owned(),
+        }));
+        assert_eq!(*wikipedia.client.sleeps.lock().unwrap(),
+                vec![Duration::from_millis(1500); 3]);
+    }
+
+    #[cfg(feature = "http-client")]
+    #[test]
+    fn default_client_retry_sleep() {
+        let sleeps = Arc::new(Mutex::new(Vec::new()));
+        let mut client = super::http::default::Client::default();
+        client.set_max_retries(2);
+        client.set_retry_delay(Duration::from_millis(100));
+        let s = sleeps.clone();
+        client.set_sleep(move |d| s.lock().unwrap().push(d));
+        // nothing listens on port 1, so the connection fails immediately
+        assert!(client.get("http://127.0.0.1:1/", std::iter::empty()).is_err());
+        assert_eq!(*sleeps.lock().unwrap(),
+                vec![Duration::from_millis(100), Duration::from_millis(200)]);
+    }
+
+    #[test]
+    fn maxlag() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.maxlag = Some(5);
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[]}}".to_owned());
+        wikipedia.search("hello world").unwrap();
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("srsearch".to_owned(), "hello world".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("maxlag".to_owned(), "5".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn assert() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.assert = Some("user".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"assertuserfailed\",\"info\":\"You are no longer logged in\"}}".to_owned());
+        match wikipedia.search("hello world") {
+            Err(super::Error::ApiError { code, .. }) => assert_eq!(code, "assertuserfailed".to_owned()),
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0].last().unwrap(),
+                &("assert".to_owned(), "user".to_owned()));
+    }
+
+    #[test]
+    fn error_eq() {
+        assert_eq!(super::Error::JSONPathError, super::Error::JSONPathError);
+        assert_eq!(super::Error::InvalidParameter("radius".to_owned()), super::Error::InvalidParameter("radius".to_owned()));
+        assert!(super::Error::InvalidParameter("radius".to_owned()) != super::Error::InvalidParameter("language".to_owned()));
+        assert!(super::Error::InvalidParameter("radius".to_owned()) != super::Error::InvalidTitle("radius".to_owned()));
+        assert_eq!(
+                super::Error::ApiError { code: "maxlag".to_owned(), info: "Waiting".to_owned() },
+                super::Error::ApiError { code: "maxlag".to_owned(), info: "Waiting".to_owned() });
+        assert_eq!(
+                super::Error::IOError(std::io::Error::new(std::io::ErrorKind::Other, "a")),
+                super::Error::IOError(std::io::Error::new(std::io::ErrorKind::Other, "b")));
+    }
+
+    #[test]
+    fn query_raw() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"statistics\":{\"articles\":6000000}}}".to_owned());
+        let response = wikipedia.query_raw(&[("action", "query"), ("meta", "siteinfo"), ("siprop", "statistics")]).unwrap();
+        assert_eq!(response["query"]["statistics"]["articles"].as_u64(), Some(6000000));
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("action".to_owned(), "query".to_owned()),
+                    ("meta".to_owned(), "siteinfo".to_owned()),
+                    ("siprop".to_owned(), "statistics".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn variant() {
+        let mut wikipedia = Wikipedia::<MockClient>::default().with_language("zh");
+        wikipedia.variant = Some("zh-hant".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"電腦\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"parse\":{\"text\":{\"*\":\"<p>電腦</p>\"}}}".to_owned());
+        wikipedia.search("computer").unwrap();
+        wikipedia.parse_wikitext("電腦").unwrap();
+        let arguments = wikipedia.client.arguments.lock().unwrap();
+        assert_eq!(arguments[0][arguments[0].len() - 2..], [
+                ("variant".to_owned(), "zh-hant".to_owned()),
+                ("converttitles".to_owned(), "".to_owned()),
+                ]);
+        assert_eq!(arguments[1].last().unwrap(),
+                &("variant".to_owned(), "zh-hant".to_owned()));
+    }
+
+    #[test]
+    fn user_agent() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{}".to_owned());
+        wikipedia.search("hello world").unwrap_err();
+        assert_eq!(&*wikipedia.client.user_agent.unwrap(), "wikipedia (https://github.com/seppo0010/wikipedia-rs)");
+
+        let mut client = MockClient::default();
+        client.user_agent("hello world".to_owned());
+        client.response.lock().unwrap().push("{}".to_owned());
+        wikipedia.client = client;
+        wikipedia.search("hello world").unwrap_err();
+        assert_eq!(&*wikipedia.client.user_agent.unwrap(), "hello world");
+    }
+
+    #[test]
+    fn search() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search("hello world").unwrap(),
+                vec![
+                "hello".to_owned(),
+                "world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("srsearch".to_owned(), "hello world".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn search_total() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"searchinfo\":{\"totalhits\":12340},\"search\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search_total("hello world").unwrap(),
+                (vec!["hello".to_owned(), "world".to_owned()], 12340));
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srinfo".to_owned(), "totalhits".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("srsearch".to_owned(), "hello world".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn search_offset() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search_offset("hello world", 10).unwrap(),
+                vec![
+                "hello".to_owned(),
+                "world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("sroffset".to_owned(), "10".to_owned()),
+                    ("srsearch".to_owned(), "hello world".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn search_in_namespace() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"Template:Hello\"}, {\"title\":\"Help:World\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search_in_namespace("hello world", &[10, 12]).unwrap(),
+                vec![
+                "Template:Hello".to_owned(),
+                "Help:World".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("srnamespace".to_owned(), "10|12".to_owned()),
+                    ("srsearch".to_owned(), "hello world".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn search_suggestion() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"searchinfo\":{\"suggestion\":\"hello world\"},\"search\":[]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"searchinfo\":{},\"search\":[{\"title\":\"Hello\"}]}}".to_owned());
+        assert_eq!(wikipedia.search_suggestion("helo wrold").unwrap(), Some("hello world".to_owned()));
+        assert_eq!(wikipedia.search_suggestion("hello").unwrap(), None);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0],
+                vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srinfo".to_owned(), "suggestion".to_owned()),
+                    ("srprop".to_owned(), "".to_owned()),
+                    ("srlimit".to_owned(), "1".to_owned()),
+                    ("srsearch".to_owned(), "helo wrold".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]);
+    }
+
+    #[test]
+    fn search_detailed() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"ns\":0,\"title\":\"hello\",\"pageid\":1,\"size\":100,\"wordcount\":20,\"snippet\":\"<span class=\\\"searchmatch\\\">hello</span> world\",\"timestamp\":\"2018-03-04T10:20:00Z\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search_detailed("hello").unwrap(),
+                vec![
+                SearchResult {
+                    title: "hello".to_owned(),
+                    pageid: 1,
+                    snippet: "<span class=\"searchmatch\">hello</span> world".to_owned(),
+                    size: 100,
+                    wordcount: 20,
+                    timestamp: "2018-03-04T10:20:00Z".to_owned(),
+                },
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "search".to_owned()),
+                    ("srprop".to_owned(), "snippet|size|wordcount|timestamp".to_owned()),
+                    ("srlimit".to_owned(), "10".to_owned()),
+                    ("srsearch".to_owned(), "hello".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn search_titles() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"prefixsearch\":[{\"ns\":0,\"title\":\"Hello\",\"pageid\":1}, {\"ns\":0,\"title\":\"Hello world\",\"pageid\":2}]}}".to_owned());
+        assert_eq!(
+                wikipedia.search_titles("hel").unwrap(),
+                vec![
+                "Hello".to_owned(),
+                "Hello world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "prefixsearch".to_owned()),
+                    ("pslimit".to_owned(), "10".to_owned()),
+                    ("pssearch".to_owned(), "hel".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn opensearch() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("[\"hel\",[\"Hello\",\"Helium\"],[\"\",\"\"],[\"https://en.wikipedia.org/wiki/Hello\",\"https://en.wikipedia.org/wiki/Helium\"]]".to_owned());
+        assert_eq!(
+                wikipedia.opensearch("hel").unwrap(),
+                vec![
+                ("Hello".to_owned(), "https://en.wikipedia.org/wiki/Hello".to_owned()),
+                ("Helium".to_owned(), "https://en.wikipedia.org/wiki/Helium".to_owned()),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("search".to_owned(), "hel".to_owned()),
+                    ("limit".to_owned(), "10".to_owned()),
+                    ("namespace".to_owned(), "0".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "opensearch".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_geosearch_nearby() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"geosearch\":[{\"title\":\"Eiffel Tower\"}, {\"title\":\"Champ de Mars\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Eiffel Tower".to_owned()).geosearch_nearby(1000).unwrap(),
+                vec![
+                "Eiffel Tower".to_owned(),
+                "Champ de Mars".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "geosearch".to_owned()),
+                    ("gsradius".to_owned(), "1000".to_owned()),
+                    ("gspage".to_owned(), "Eiffel Tower".to_owned()),
+                    ("gslimit".to_owned(), "10".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+        assert!(wikipedia.page_from_pageid("1".to_owned()).geosearch_nearby(5).is_err());
+    }
+
+    #[test]
+    fn geosearch() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"geosearch\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.geosearch(-34.603333, -58.381667, 10).unwrap(),
+                vec![
+                "hello".to_owned(),
+                "world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "geosearch".to_owned()),
+                    ("gsradius".to_owned(), "10".to_owned()),
+                    ("gscoord".to_owned(), "-34.603333|-58.381667".to_owned()),
+                    ("gslimit".to_owned(), "10".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn geosearch_detailed() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"geosearch\":[{\"pageid\":1,\"title\":\"hello\",\"lat\":-34.6,\"lon\":-58.38,\"dist\":3.5}]}}".to_owned());
+        assert_eq!(
+                wikipedia.geosearch_detailed(-34.603333, -58.381667, 10).unwrap(),
+                vec![
+                GeoResult {
+                    title: "hello".to_owned(),
+                    pageid: 1,
+                    lat: -34.6,
+                    lon: -58.38,
+                    dist: 3.5,
+                },
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "geosearch".to_owned()),
+                    ("gsradius".to_owned(), "10".to_owned()),
+                    ("gscoord".to_owned(), "-34.603333|-58.381667".to_owned()),
+                    ("gslimit".to_owned(), "10".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn random_count() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.random_count(10).unwrap(),
+                vec![
+                "hello".to_owned(),
+                "world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "0".to_owned()),
+                    ("rnlimit".to_owned(), "10".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn random_count_continue() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\":{\"rncontinue\":\"0.5|123\",\"continue\":\"-||\"},\"query\":{\"random\":[{\"title\":\"hello\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.random_count(1000).unwrap(),
+                vec![
+                "hello".to_owned(),
+                "world".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "0".to_owned()),
+                    ("rnlimit".to_owned(), "500".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ], vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "0".to_owned()),
+                    ("rnlimit".to_owned(), "500".to_owned()),
+                    ("rncontinue".to_owned(), "0.5|123".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn random_count_with_pageids() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"id\":1,\"ns\":0,\"title\":\"hello\"}, {\"id\":2,\"ns\":0,\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.random_count_with_pageids(2).unwrap(),
+                vec![
+                (1, "hello".to_owned()),
+                (2, "world".to_owned()),
+                ]);
+    }
+
+    #[test]
+    fn random_in_namespace() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"Category:Hello\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"Template:World\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.random_in_namespace(1, 14, false).unwrap(),
+                vec!["Category:Hello".to_owned()]);
+        assert_eq!(
+                wikipedia.random_in_namespace(1, 10, true).unwrap(),
+                vec!["Template:World".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "14".to_owned()),
+                    ("rnlimit".to_owned(), "1".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                ],
+                vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "10".to_owned()),
+                    ("rnlimit".to_owned(), "1".to_owned()),
+                    ("rnfilterredir".to_owned(), "all".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn random() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.random().unwrap(),
+                Some("hello".to_owned())
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("list".to_owned(), "random".to_owned()),
+                    ("rnnamespace".to_owned(), "0".to_owned()),
+                    ("rnlimit".to_owned(), "1".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn summaries_parallel() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        for _ in 0..5 {
+            wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
+        }
+        let titles: Vec<_> = (0..5).map(|i| format!("Title {}", i)).collect();
+        let summaries = wikipedia.summaries_parallel(&titles, 3);
+        assert_eq!(summaries.iter().map(|x| x.0.clone()).collect::<Vec<_>>(), titles);
+        assert!(summaries.iter().all(|x| x.1.as_ref().unwrap() == "hello"));
+        let mut requested: Vec<_> = wikipedia.client.arguments.lock().unwrap().iter()
+            .map(|x| x.last().unwrap().1.clone())
+            .collect();
+        requested.sort();
+        assert_eq!(requested, titles);
+    }
+
+    #[test]
+    fn summaries_from_titles_chunks() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let titles: Vec<_> = (0..25).map(|i| format!("Title {}", i)).collect();
+        for chunk in titles.chunks(20) {
+            let pages: Vec<_> = chunk.iter().enumerate()
+                .map(|(i, t)| format!("\"{}\":{{\"title\":\"{}\",\"extract\":\"about {}\"}}", i, t, t))
+                .collect();
+            wikipedia.client.response.lock().unwrap().push(format!("{{\"query\":{{\"pages\":{{{}}}}}}}", pages.join(",")));
+        }
+        let summaries = wikipedia.summaries_from_titles(&titles).unwrap();
+        assert_eq!(summaries.len(), 25);
+        assert_eq!(summaries[24], ("Title 24".to_owned(), "about Title 24".to_owned()));
+        let arguments = wikipedia.client.arguments.lock().unwrap();
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments[0].last().unwrap().1, titles[..20].join("|"));
+        assert_eq!(arguments[1].last().unwrap().1, titles[20..].join("|"));
+    }
+
+    #[test]
+    fn summaries_from_titles() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"hello_world\",\"to\":\"Hello world\"}],\"redirects\":[{\"from\":\"Hello world\",\"to\":\"Hello\"}],\"pages\":{\"1\":{\"title\":\"Hello\",\"extract\":\"hi\"},\"2\":{\"title\":\"World\",\"extract\":\"earth\"},\"-1\":{\"title\":\"Missing\",\"missing\":\"\"}}}}".to_owned());
+        let titles = vec!["World".to_owned(), "hello_world".to_owned(), "Missing".to_owned()];
+        assert_eq!(
+                wikipedia.summaries_from_titles(&titles).unwrap(),
+                vec![
+                ("World".to_owned(), "earth".to_owned()),
+                ("hello_world".to_owned(), "hi".to_owned()),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("exlimit".to_owned(), "max".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World|hello_world|Missing".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn parse_wikitext() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"parse\":{\"title\":\"API\",\"pageid\":0,\"text\":{\"*\":\"<p><i>hello</i></p>\"}}}".to_owned());
+        assert_eq!(wikipedia.parse_wikitext("''hello''").unwrap(), "<p><i>hello</i></p>".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "text".to_owned()),
+                    ("contentmodel".to_owned(), "wikitext".to_owned()),
+                    ("text".to_owned(), "''hello''".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "parse".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn expand_templates() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"expandtemplates\":{\"wikitext\":\"2018\"}}".to_owned());
+        assert_eq!(wikipedia.expand_templates("{{CURRENTYEAR}}").unwrap(), "2018".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "wikitext".to_owned()),
+                    ("text".to_owned(), "{{CURRENTYEAR}}".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "expandtemplates".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn normalize_titles_chunks() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let titles: Vec<_> = (0..60).map(|i| format!("title_{}", i)).collect();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"title_0\",\"to\":\"Title 0\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"title_59\",\"to\":\"Title 59\"}]}}".to_owned());
+        let normalized = wikipedia.normalize_titles(&titles).unwrap();
+        assert_eq!(normalized.len(), 60);
+        assert_eq!(normalized[0], ("title_0".to_owned(), "Title 0".to_owned()));
+        assert_eq!(normalized[1], ("title_1".to_owned(), "title_1".to_owned()));
+        assert_eq!(normalized[59], ("title_59".to_owned(), "Title 59".to_owned()));
+        let arguments = wikipedia.client.arguments.lock().unwrap();
+        assert_eq!(arguments.len(), 2);
+        assert_eq!(arguments[0].last().unwrap().1, titles[..50].join("|"));
+        assert_eq!(arguments[1].last().unwrap().1, titles[50..].join("|"));
+    }
+
+    #[test]
+    fn normalize_titles() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"hello_world\",\"to\":\"Hello world\"}],\"pages\":{\"1\":{\"title\":\"Hello world\"},\"2\":{\"title\":\"World\"}}}}".to_owned());
+        let titles = vec!["World".to_owned(), "hello_world".to_owned()];
+        assert_eq!(
+                wikipedia.normalize_titles(&titles).unwrap(),
+                vec![
+                ("World".to_owned(), "World".to_owned()),
+                ("hello_world".to_owned(), "Hello world".to_owned()),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World|hello_world".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn long_query_post() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[]}}".to_owned());
+        wikipedia.search("hello world").unwrap();
+        wikipedia.search(&"hello world ".repeat(200)).unwrap();
+        assert_eq!(*wikipedia.client.method.lock().unwrap(), vec!["GET".to_owned(), "POST".to_owned()]);
+    }
+
+    #[test]
+    fn page_content() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
+        let page = wikipedia.page_from_pageid("4138548".to_owned());
+        let html = page.get_content().unwrap();
+        assert_eq!(
+                html,
+                "hello".to_owned()
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts|revisions".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("rvprop".to_owned(), "ids".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("pageids".to_owned(), "4138548".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_content_formatted() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"Hello\\n\\n\\u0001\\u00022\\u0002\\u0001Examples\\nFirst\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_content_formatted(SectionFormat::Raw).unwrap(),
+                "Hello\n\n\u{1}\u{2}2\u{2}\u{1}Examples\nFirst".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exsectionformat".to_owned(), "raw".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_content_with_revid() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\",\"revisions\":[{\"revid\":123,\"parentid\":122}]}}}}".to_owned());
+        let page = wikipedia.page_from_pageid("4138548".to_owned());
+        assert_eq!(page.get_content_with_revid().unwrap(), ("hello".to_owned(), 123));
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts|revisions".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("rvprop".to_owned(), "ids".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("pageids".to_owned(), "4138548".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_html_content() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"slots\":{\"main\":{\"*\":\"hello\"}}}]}}}}".to_owned());
+        let page = wikipedia.page_from_pageid("4138548".to_owned());
+        let html = page.get_html_content().unwrap();
+        assert_eq!(
+                html,
+                "hello".to_owned()
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "content".to_owned()),
+                    ("rvslots".to_owned(), "main".to_owned()),
+                    ("rvlimit".to_owned(), "1".to_owned()),
+                    ("rvparse".to_owned(), "".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("pageids".to_owned(), "4138548".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_wikitext_legacy_revisions() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.legacy_revisions = true;
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"*\":\"hello\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_pageid("4138548".to_owned());
+        assert_eq!(page.get_wikitext().unwrap(), "hello".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "content".to_owned()),
+                    ("rvlimit".to_owned(), "1".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("pageids".to_owned(), "4138548".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_wikitext() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"slots\":{\"main\":{\"contentmodel\":\"wikitext\",\"*\":\"''hello''\"}}}]}}}}".to_owned());
+        let page = wikipedia.page_from_pageid("4138548".to_owned());
+        assert_eq!(page.get_wikitext().unwrap(), "''hello''".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "content".to_owned()),
+                    ("rvslots".to_owned(), "main".to_owned()),
+                    ("rvlimit".to_owned(), "1".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("pageids".to_owned(), "4138548".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_infobox() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let wikitext = "{{Short description|City}}\n{{Infobox settlement\n| name = '''Buenos Aires'''<ref name=\"a\">{{cite web|url=x}}</ref>\n| image = <!-- none -->\n| country = [[Argentina]]\n| leader = [[Jorge Macri|Macri]] {{small|(PRO)}}\n| motto = First line\nsecond line\n}}\n'''Buenos Aires''' is...";
+        wikipedia.client.response.lock().unwrap().push(format!("{{\"query\":{{\"pages\":{{\"a\":{{\"revisions\":[{{\"slots\":{{\"main\":{{\"*\":{:?}}}}}}}]}}}}}}}}", wikitext));
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"slots\":{\"main\":{\"*\":\"hello\"}}}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("Buenos Aires".to_owned());
+        let infobox = page.get_infobox().unwrap().unwrap();
+        assert_eq!(
+                infobox.into_iter().collect::<Vec<_>>(),
+                vec![
+                ("country".to_owned(), "Argentina".to_owned()),
+                ("leader".to_owned(), "Macri {{small|(PRO)}}".to_owned()),
+                ("motto".to_owned(), "First line\nsecond line".to_owned()),
+                ("name".to_owned(), "Buenos Aires".to_owned()),
+                ]);
+        assert_eq!(page.get_infobox().unwrap(), None);
+    }
+
+    #[test]
+    fn page_content_by_revision() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"slots\":{\"main\":{\"*\":\"old\"}}}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(page.get_content_by_revision(123).unwrap(), "old".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "content".to_owned()),
+                    ("rvslots".to_owned(), "main".to_owned()),
+                    ("rvstartid".to_owned(), "123".to_owned()),
+                    ("rvlimit".to_owned(), "1".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ]]);
+    }
+
+    #[test]
+    fn page_extracts() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"Hello\\nworld\\n\\n\\n== Examples ==\\nFirst\\n\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_extracts().unwrap(),
+                ("Hello\nworld".to_owned(), "Hello\nworld\n\n\n== Examples ==\nFirst\n".to_owned()));
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn page_summary() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
+        let summary = page.get_summary().unwrap();
+        assert_eq!(
+                summary,
+                "hello".to_owned()
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_summary_limited() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello.\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(page.get_summary_limited(Some(2), None).unwrap(), "hello.".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("exsentences".to_owned(), "2".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_redirect_summary() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"to\":\"hello world\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
+        let summary = page.get_summary().unwrap();
+        assert_eq!(
+                summary,
+                "hello".to_owned()
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned())
+                ],
+                vec![
+                    ("prop".to_owned(), "extracts".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "hello world".to_owned())
+                    ]
+                ]
+                );
+    }
+
+    #[test]
+    fn page_no_follow_redirects() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"title\":\"Bikeshedding\",\"redirect\":\"\",\"extract\":\"\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("Bikeshedding".to_owned()).with_follow_redirects(false);
+        assert_eq!(page.get_content().unwrap(), "".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts|revisions".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("rvprop".to_owned(), "ids".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Bikeshedding".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_redirect_chain() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"from\":\"A\",\"to\":\"B\"},{\"from\":\"B\",\"to\":\"C\"}],\"pages\":{\"1\":{\"title\":\"C\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"from\":\"A\",\"to\":\"B\"},{\"from\":\"B\",\"to\":\"A\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("A".to_owned()).get_redirect_target().unwrap(),
+                Some("C".to_owned()));
+        match wikipedia.page_from_title("A".to_owned()).get_redirect_target() {
+            Err(super::Error::RedirectLoop(_)) => (),
+            r => panic!("unexpected result {:?}", r),
+        }
+    }
+
+    #[test]
+    fn page_is_disambiguation() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"123\":{\"title\":\"Mercury\",\"pageprops\":{\"disambiguation\":\"\"}}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"456\":{\"title\":\"World\"}}}}".to_owned());
+        assert!(wikipedia.page_from_title("Mercury".to_owned()).is_disambiguation().unwrap());
+        assert!(!wikipedia.page_from_title("World".to_owned()).is_disambiguation().unwrap());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("prop".to_owned(), "info|pageprops".to_owned()),
+                    ("inprop".to_owned(), "url".to_owned()),
+                    ("ppprop".to_owned(), "disambiguation".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Mercury".to_owned())
+                ],
+                vec![
+                    ("prop".to_owned(), "info|pageprops".to_owned()),
+                    ("inprop".to_owned(), "url".to_owned()),
+                    ("ppprop".to_owned(), "disambiguation".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn page_exists() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"World\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"title\":\"Wrold\",\"missing\":\"\"}}}}".to_owned());
+        assert!(wikipedia.page_from_title("World".to_owned()).exists().unwrap());
+        assert!(!wikipedia.page_from_title("Wrold".to_owned()).exists().unwrap());
+    }
+
+    #[test]
+    fn page_exists_invalid() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"title\":\"[[\",\"invalidreason\":\"The requested page title contains invalid characters: \\\"[[\\\".\",\"invalid\":\"\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"title\":\"\",\"invalidreason\":\"The requested page title is empty or contains only the name of a namespace.\",\"invalid\":\"\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("[[".to_owned()).exists(),
+                Err(super::Error::InvalidTitle("The requested page title contains invalid characters: \"[[\".".to_owned())));
+        assert_eq!(
+                wikipedia.page_from_title("".to_owned()).exists(),
+                Err(super::Error::InvalidTitle("The requested page title is empty or contains only the name of a namespace.".to_owned())));
+    }
+
+    #[test]
+    fn page_invalid_title() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"title\":\"Foo[]\",\"invalidreason\":\"The requested page title contains invalid characters: \\\"[]\\\".\",\"invalid\":\"\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"ns\":0,\"title\":\"Foo\",\"missing\":\"\"}}}}".to_owned());
+        match wikipedia.page_from_title("Foo[]".to_owned()).get_pageid() {
+            Err(super::Error::InvalidTitle(reason)) => assert_eq!(reason, "The requested page title contains invalid characters: \"[]\".".to_owned()),
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert_eq!(wikipedia.page_from_title("Foo".to_owned()).get_pageid().unwrap(), "-1".to_owned());
+    }
+
+    #[test]
+    fn page_display_title() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"Drosophila melanogaster\",\"displaytitle\":\"<i>Drosophila melanogaster</i>\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Drosophila melanogaster".to_owned()).get_display_title().unwrap(),
+                "<i>Drosophila melanogaster</i>".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "info".to_owned()),
+                    ("inprop".to_owned(), "displaytitle".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Drosophila melanogaster".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_description() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"Weezer\",\"pageprops\":{\"wikibase-shortdesc\":\"American rock band\"}}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"2\":{\"title\":\"Foo\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Weezer".to_owned()).get_description().unwrap(),
+                Some("American rock band".to_owned()));
+        assert_eq!(wikipedia.page_from_title("Foo".to_owned()).get_description().unwrap(), None);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0],
+                vec![
+                    ("prop".to_owned(), "pageprops".to_owned()),
+                    ("ppprop".to_owned(), "wikibase-shortdesc".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Weezer".to_owned())
+                    ]);
+    }
+
+    #[test]
+    fn page_preview() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"Weezer\",\"extract\":\"Weezer is a band.\",\"thumbnail\":{\"source\":\"https://upload.wikimedia.org/a.jpg\",\"width\":320,\"height\":200},\"pageprops\":{\"wikibase-shortdesc\":\"American rock band\"}}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Weezer".to_owned()).get_preview().unwrap(),
+                Preview {
+                    title: "Weezer".to_owned(),
+                    description: Some("American rock band".to_owned()),
+                    extract: "Weezer is a band.".to_owned(),
+                    thumbnail: Some("https://upload.wikimedia.org/a.jpg".to_owned()),
+                });
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extracts|pageimages|pageprops".to_owned()),
+                    ("explaintext".to_owned(), "".to_owned()),
+                    ("exintro".to_owned(), "".to_owned()),
+                    ("piprop".to_owned(), "thumbnail".to_owned()),
+                    ("pithumbsize".to_owned(), "320".to_owned()),
+                    ("ppprop".to_owned(), "wikibase-shortdesc".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Weezer".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_protection() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"World\",\"protection\":[{\"type\":\"edit\",\"level\":\"autoconfirmed\",\"expiry\":\"infinity\"},{\"type\":\"move\",\"level\":\"sysop\",\"expiry\":\"2030-01-01T00:00:00Z\"}]}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("World".to_owned()).get_protection().unwrap(),
+                vec![
+                Protection {
+                    kind: "edit".to_owned(),
+                    level: "autoconfirmed".to_owned(),
+                    expiry: "infinity".to_owned(),
+                },
+                Protection {
+                    kind: "move".to_owned(),
+                    level: "sysop".to_owned(),
+                    expiry: "2030-01-01T00:00:00Z".to_owned(),
+                },
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "info".to_owned()),
+                    ("inprop".to_owned(), "protection".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_url() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"to\":\"Law of triviality\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"4138548\":{\"fullurl\":\"https://en.wikipedia.org/wiki/Law_of_triviality\",\"canonicalurl\":\"https://en.wikipedia.org/wiki/Law_of_triviality\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("Bikeshedding".to_owned());
+        assert_eq!(page.get_url().unwrap(), "https://en.wikipedia.org/wiki/Law_of_triviality".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("prop".to_owned(), "info|pageprops".to_owned()),
+                    ("inprop".to_owned(), "url".to_owned()),
+                    ("ppprop".to_owned(), "disambiguation".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Bikeshedding".to_owned())
+                ],
+                vec![
+                    ("prop".to_owned(), "info|pageprops".to_owned()),
+                    ("inprop".to_owned(), "url".to_owned()),
+                    ("ppprop".to_owned(), "disambiguation".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Law of triviality".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn page_info() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"to\":\"Law of triviality\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"4138548\":{\"pageid\":4138548,\"ns\":0,\"title\":\"Law of triviality\",\"touched\":\"2018-03-04T10:20:00Z\",\"length\":12345}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"4138548\":{\"pageid\":4138548,\"ns\":0,\"title\":\"Law of triviality\",\"touched\":\"2018-03-04T10:20:00Z\",\"length\":12345}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Bikeshedding".to_owned()).get_info().unwrap(),
+                PageInfo {
+                    pageid: 4138548,
+                    title: "Law of triviality".to_owned(),
+                    length: 12345,
+                    touched: "2018-03-04T10:20:00Z".to_owned(),
+                    ns: 0,
+                    redirect: true,
+                });
+        assert!(!wikipedia.page_from_pageid("4138548".to_owned()).get_info().unwrap().redirect);
+    }
+
+    #[test]
+    fn page_resolve() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"bikeshedding\",\"to\":\"Bikeshedding\"}],\"redirects\":[{\"from\":\"Bikeshedding\",\"to\":\"Law of triviality\"}],\"pages\":{\"4138548\":{\"pageid\":4138548,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"-1\":{\"title\":\"Wrold\",\"missing\":\"\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("bikeshedding".to_owned()).resolve().unwrap(),
+                ResolvedPage {
+                    requested: "bikeshedding".to_owned(),
+                    normalized: Some("Bikeshedding".to_owned()),
+                    redirected_to: Some("Law of triviality".to_owned()),
+                    final_title: "Law of triviality".to_owned(),
+                    pageid: Some(4138548),
+                });
+        assert_eq!(
+                wikipedia.page_from_title("Wrold".to_owned()).resolve().unwrap(),
+                ResolvedPage {
+                    requested: "Wrold".to_owned(),
+                    normalized: None,
+                    redirected_to: None,
+                    final_title: "Wrold".to_owned(),
+                    pageid: None,
+                });
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0],
+                vec![
+                    ("prop".to_owned(), "info".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "bikeshedding".to_owned())
+                    ]);
+    }
+
+    #[test]
+    fn page_normalized_title() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"law_of_triviality\",\"to\":\"Law of triviality\"}],\"pages\":{\"1\":{\"pageid\":1,\"ns\":0,\"title\":\"Law of triviality\",\"redirect\":\"\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("law_of_triviality".to_owned()).normalized_title().unwrap(),
+                "Law of triviality".to_owned());
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "info".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "law_of_triviality".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_redirect_target() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"from\":\"Bikeshedding\",\"to\":\"Law of triviality\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"4138548\":{\"title\":\"Law of triviality\"}}}}".to_owned());
+        assert_eq!(
+                wikipedia.page_from_title("Bikeshedding".to_owned()).get_redirect_target().unwrap(),
+                Some("Law of triviality".to_owned()));
+        assert_eq!(
+                wikipedia.page_from_title("Law of triviality".to_owned()).get_redirect_target().unwrap(),
+                None);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Bikeshedding".to_owned())
+                ],
+                vec![
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Law of triviality".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn page_wikidata_id() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"8091\":{\"title\":\"Douglas Adams\",\"pageprops\":{\"wikibase_item\":\"Q42\"}}}}}".to_owned());
+        let page = wikipedia.page_from_title("Douglas Adams".to_owned());
+        assert_eq!(page.get_wikidata_id().unwrap(), Some("Q42".to_owned()));
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "pageprops".to_owned()),
+                    ("ppprop".to_owned(), "wikibase_item".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Douglas Adams".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_main_image() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"thumbnail\":{\"source\":\"https://example.com/thumb.jpg\",\"width\":200,\"height\":150}}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(page.get_main_image(200).unwrap(), Some("https://example.com/thumb.jpg".to_owned()));
+        assert_eq!(page.get_main_image(200).unwrap(), None);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0],
+                vec![
+                    ("prop".to_owned(), "pageimages".to_owned()),
+                    ("piprop".to_owned(), "thumbnail".to_owned()),
+                    ("pithumbsize".to_owned(), "200".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]);
+    }
+
+    #[test]
+    fn page_images_filtered() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"title\":\"File:A.JPG\",\"imageinfo\":[{\"url\":\"https://upload.wikimedia.org/a.jpg\",\"descriptionurl\":\"https://en.wikipedia.org/wiki/File:A.JPG\"}]},\"2\":{\"title\":\"File:B.svg\",\"imageinfo\":[{\"url\":\"https://upload.wikimedia.org/b.svg\",\"descriptionurl\":\"https://en.wikipedia.org/wiki/File:B.svg\"}]},\"3\":{\"title\":\"File:C.png\",\"imageinfo\":[{\"url\":\"https://upload.wikimedia.org/c.png\",\"descriptionurl\":\"https://en.wikipedia.org/wiki/File:C.png\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_images_filtered(&["jpg", ".png"]).unwrap().map(|i| i.title).collect::<Vec<_>>(),
+                vec!["File:A.JPG".to_owned(), "File:C.png".to_owned()]);
+    }
+
+    #[test]
+    fn page_images() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"title\":\"Image 1\", \"imageinfo\":[{\"url\": \"http://example.com/image1.jpg\", \"descriptionurl\": \"http://example.com/image1.jpg.html\", \"width\": 640, \"height\": 480, \"size\": 12345, \"mime\": \"image/jpeg\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"title\":\"Image 2\", \"imageinfo\":[{\"url\": \"http://example.com/image2.jpg\", \"descriptionurl\": \"http://example.com/image2.jpg.html\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
+        let images = page.get_images().unwrap().collect::<Vec<_>>();
+        assert_eq!(
+                images,
+                vec![
+                iter::Image {
+                    url: "http://example.com/image1.jpg".to_owned(),
+                    title: "Image 1".to_owned(),
+                    description_url: "http://example.com/image1.jpg.html".to_owned(),
+                    width: Some(640),
+                    height: Some(480),
+                    size: Some(12345),
+                    mime: Some("image/jpeg".to_owned()),
+                },
+                iter::Image {
+                    url: "http://example.com/image2.jpg".to_owned(),
+                    title: "Image 2".to_owned(),
+                    description_url: "http://example.com/image2.jpg.html".to_owned(),
+                    width: None,
+                    height: None,
+                    size: None,
+                    mime: None,
+                }
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("generator".to_owned(), "images".to_owned()),
+                    ("gimlimit".to_owned(), "max".to_owned()),
+                    ("prop".to_owned(), "imageinfo".to_owned()),
+                    ("iiprop".to_owned(), "url|size|mime".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned()),
+                    ("continue".to_owned(), "".to_owned())
+                ],
+                vec![
+                    ("generator".to_owned(), "images".to_owned()),
+                    ("gimlimit".to_owned(), "max".to_owned()),
+                    ("prop".to_owned(), "imageinfo".to_owned()),
+                    ("iiprop".to_owned(), "url|size|mime".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned()),
+                    ("lol".to_owned(), "1".to_owned())
+                ]
+                ]
+                );
+    }
+
+    #[test]
+    fn image_download() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("GIF89a".to_owned());
+        let image = iter::Image {
+            url: "https://upload.wikimedia.org/a.gif".to_owned(),
+            title: "File:A.gif".to_owned(),
+            description_url: "https://en.wikipedia.org/wiki/File:A.gif".to_owned(),
+            width: None,
+            height: None,
+            size: None,
+            mime: None,
+        };
+        assert_eq!(image.download(&wikipedia.client).unwrap(), b"GIF89a".to_vec());
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://upload.wikimedia.org/a.gif".to_owned()]);
+    }
+
+    #[test]
+    fn image_thumbnail_url() {
+        let image = |url: &str| iter::Image {
+            url: url.to_owned(),
+            title: "".to_owned(),
+            description_url: "".to_owned(),
+            width: None,
+            height: None,
+            size: None,
+            mime: None,
+        };
+        assert_eq!(
+                image("https://upload.wikimedia.org/wikipedia/commons/a/ab/Buenos_Aires.jpg").thumbnail_url(200),
+                "https://upload.wikimedia.org/wikipedia/commons/thumb/a/ab/Buenos_Aires.jpg/200px-Buenos_Aires.jpg".to_owned());
+        assert_eq!(
+                image("https://upload.wikimedia.org/wikipedia/en/c/cd/Flag.svg").thumbnail_url(120),
+                "https://upload.wikimedia.org/wikipedia/en/thumb/c/cd/Flag.svg/120px-Flag.svg.png".to_owned());
+        assert_eq!(
+                image("https://example.com/a.jpg").thumbnail_url(200),
+                "https://example.com/a.jpg".to_owned());
+    }
+
+    #[test]
+    fn page_coordinates() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"coordinates\":[{\"lat\": 2.1, \"lon\":-1.3}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        let coordinates = page.get_coordinates().unwrap().unwrap();
+        assert_eq!(
+                coordinates,
+                (2.1, -1.3)
+                );
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "coordinates".to_owned()),
+                    ("colimit".to_owned(), "max".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_all_coordinates() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"coordinates\":[{\"lat\": 2.1, \"lon\":-1.3, \"globe\":\"earth\", \"primary\":\"\", \"type\":\"city\", \"dim\":10000}, {\"lat\": 4.5, \"lon\":137.4, \"globe\":\"mars\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_all_coordinates().unwrap(),
+                vec![
+                Coordinate {
+                    lat: 2.1,
+                    lon: -1.3,
+                    globe: "earth".to_owned(),
+                    primary: true,
+                    kind: Some("city".to_owned()),
+                    dim: Some(10000),
+                },
+                Coordinate {
+                    lat: 4.5,
+                    lon: 137.4,
+                    globe: "mars".to_owned(),
+                    primary: false,
+                    kind: None,
+                    dim: None,
+                },
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "coordinates".to_owned()),
+                    ("coprop".to_owned(), "type|dim|globe".to_owned()),
+                    ("coprimary".to_owned(), "all".to_owned()),
+                    ("colimit".to_owned(), "max".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn page_no_coordinates() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert!(page.get_coordinates().unwrap().is_none());
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "coordinates".to_owned()),
+                    ("colimit".to_owned(), "max".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn get_references() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"extlinks\":[{\"*\": \"//example.com/reference1.html\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extlinks\":[{\"*\": \"https://example.com/reference2.html\"}, {\"*\": \"http://example.com/reference3.html\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_references().unwrap().collect::<Vec<_>>(),
+                vec![
+                iter::Reference {
+                    url: "https://example.com/reference1.html".to_owned(),
+                    scheme: "https".to_owned(),
+                    protocol_relative: true,
+                },
+                iter::Reference {
+                    url: "https://example.com/reference2.html".to_owned(),
+                    scheme: "https".to_owned(),
+                    protocol_relative: false,
+                },
+                iter::Reference {
+                    url: "http://example.com/reference3.html".to_owned(),
+                    scheme: "http".to_owned(),
+                    protocol_relative: false,
+                }
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "extlinks".to_owned()),
+                    ("ellimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned())
+                ],
+                vec![
+                    ("prop".to_owned(), "extlinks".to_owned()),
+                    ("ellimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("lol".to_owned(), "1".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn get_links() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_links().unwrap().collect::<Vec<_>>(),
+                vec![
+                iter::Link {
+                    title: "Hello".to_owned(),
+                },
+                iter::Link {
+                    title: "World".to_owned(),
+                }
+                ]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("prop".to_owned(), "links".to_owned()),
+                    ("plnamespace".to_owned(), "0".to_owned()),
+                    ("pllimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ],
+                vec![
+                    ("prop".to_owned(), "links".to_owned()),
+                    ("plnamespace".to_owned(), "0".to_owned()),
+                    ("pllimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("lol".to_owned(), "1".to_owned()),
+                ]
+                ]);
     }
 
     #[test]
-    fn search() {
+    fn get_langlinks() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"search\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"langlinks\":[{\"lang\":\"es\",\"url\":\"https://es.wikipedia.org/wiki/Mundo\",\"*\":\"Mundo\"},{\"*\":\"Monde\"},{\"lang\":\"fr\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                wikipedia.search("hello world").unwrap(),
+                page.get_langlinks().unwrap().collect::<Vec<_>>(),
                 vec![
-                "hello".to_owned(),
-                "world".to_owned(),
+                iter::LangLink {
+                    lang: "es".to_owned(),
+                    title: Some("Mundo".to_owned()),
+                    url: Some("https://es.wikipedia.org/wiki/Mundo".to_owned()),
+                },
+                iter::LangLink {
+                    lang: "fr".to_owned(),
+                    title: None,
+                    url: None,
+                }
                 ]);
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("list".to_owned(), "search".to_owned()),
-                    ("srprop".to_owned(), "".to_owned()),
-                    ("srlimit".to_owned(), "10".to_owned()),
-                    ("srsearch".to_owned(), "hello world".to_owned()),
+                vec![
+                vec![
+                    ("prop".to_owned(), "langlinks".to_owned()),
+                    ("llprop".to_owned(), "url".to_owned()),
+                    ("lllimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned())
-                    ]]);
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ]
+                ]);
     }
 
     #[test]
-    fn geosearch() {
+    fn get_langlink() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"geosearch\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
-        assert_eq!(
-                wikipedia.geosearch(-34.603333, -58.381667, 10).unwrap(),
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"langlinks\":[{\"lang\":\"es\",\"*\":\"Mundo\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"title\":\"World\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(page.get_langlink("es").unwrap(), Some("Mundo".to_owned()));
+        assert_eq!(page.get_langlink("xx").unwrap(), None);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[0],
                 vec![
-                "hello".to_owned(),
-                "world".to_owned(),
-                ]);
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("list".to_owned(), "geosearch".to_owned()),
-                    ("gsradius".to_owned(), "10".to_owned()),
-                    ("gscoord".to_owned(), "-34.603333|-58.381667".to_owned()),
-                    ("gslimit".to_owned(), "10".to_owned()),
+                    ("prop".to_owned(), "langlinks".to_owned()),
+                    ("lllang".to_owned(), "es".to_owned()),
+                    ("redirects".to_owned(), "".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned())
-                    ]]);
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                ]);
     }
 
     #[test]
-    fn random_count() {
+    fn in_language() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
-        assert_eq!(
-                wikipedia.random_count(10).unwrap(),
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"title\":\"World\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"langlinks\":[{\"lang\":\"es\",\"*\":\"Mundo\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"b\":{\"extract\":\"hola\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert!(page.in_language("xx").unwrap().is_none());
+        let page = page.in_language("es").unwrap().unwrap();
+        assert_eq!(page.get_title().unwrap(), "Mundo".to_owned());
+        assert_eq!(page.get_summary().unwrap(), "hola".to_owned());
+        assert_eq!(wikipedia.language, "en".to_owned());
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
                 vec![
-                "hello".to_owned(),
-                "world".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://es.wikipedia.org/w/api.php".to_owned(),
                 ]);
+    }
+
+    #[test]
+    fn page_from_url() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hola\"}}}}".to_owned());
+        let page = wikipedia.page_from_url("https://en.wikipedia.org/wiki/Law_of_triviality#History").unwrap();
+        assert_eq!(page.get_title().unwrap(), "Law of triviality".to_owned());
+        let page = wikipedia.page_from_url("https://es.wikipedia.org/wiki/Caf%C3%A9?action=view").unwrap();
+        assert_eq!(page.get_title().unwrap(), "Café".to_owned());
+        assert_eq!(page.get_summary().unwrap(), "hola".to_owned());
         assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("list".to_owned(), "random".to_owned()),
-                    ("rnnamespace".to_owned(), "0".to_owned()),
-                    ("rnlimit".to_owned(), "10".to_owned()),
-                    ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned())
-                    ]]);
+                vec!["https://es.wikipedia.org/w/api.php".to_owned()]);
+        let page = wikipedia.page_from_url("https://en.m.wikipedia.org/wiki/Law_of_triviality").unwrap();
+        assert_eq!(page.wikipedia.language, "en".to_owned());
+        assert_eq!(page.get_title().unwrap(), "Law of triviality".to_owned());
+        let page = wikipedia.page_from_url("https://es.m.wikipedia.org/wiki/Caf%C3%A9").unwrap();
+        assert_eq!(page.wikipedia.language, "es".to_owned());
+        assert!(wikipedia.page_from_url("https://en.wikipedia.org/w/index.php").is_err());
+        assert!(wikipedia.page_from_url("https://example.com/wiki/World").is_err());
+        assert!(wikipedia.page_from_url("https://en.wikipedia.org/wiki/%E9").is_err());
     }
 
     #[test]
-    fn random() {
+    fn get_links_resume() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"random\":[{\"title\":\"hello\"}, {\"title\":\"world\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        let mut links = page.get_links().unwrap();
+        assert_eq!(links.next().unwrap().title, "Hello".to_owned());
+        let cont = links.continuation().unwrap();
+        assert_eq!(cont, vec![("lol".to_owned(), "1".to_owned())]);
+        let links = iter::Iter::<_, iter::Link>::resume_from(&page, cont).unwrap();
+        assert!(links.continuation().is_none());
         assert_eq!(
-                wikipedia.random().unwrap(),
-                Some("hello".to_owned())
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("list".to_owned(), "random".to_owned()),
-                    ("rnnamespace".to_owned(), "0".to_owned()),
-                    ("rnlimit".to_owned(), "1".to_owned()),
-                    ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned())
-                    ]]);
+                links.map(|l| l.title).collect::<Vec<_>>(),
+                vec!["World".to_owned()]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[1].last().unwrap(),
+                &("lol".to_owned(), "1".to_owned()));
     }
 
     #[test]
-    fn page_content() {
+    fn count_links() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
-        let page = wikipedia.page_from_pageid("4138548".to_owned());
-        let html = page.get_content().unwrap();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}, {\"title\": \"World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Foo\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("not json".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(page.count_links().unwrap(), 3);
+        assert!(page.count_links().is_err());
+    }
+
+    #[test]
+    fn get_all_links() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("not json".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                html,
-                "hello".to_owned()
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "extracts|revisions".to_owned()),
-                    ("explaintext".to_owned(), "".to_owned()),
-                    ("rvprop".to_owned(), "ids".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
-                    ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned()),
-                    ("pageids".to_owned(), "4138548".to_owned()),
-                    ]]);
+                page.get_all_links().unwrap(),
+                vec![
+                iter::Link { title: "Hello".to_owned() },
+                iter::Link { title: "World".to_owned() },
+                ]);
+        assert!(page.get_all_links().is_err());
     }
 
     #[test]
-    fn page_html_content() {
+    fn get_links_skip_malformed() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"*\":\"hello\"}]}}}}".to_owned());
-        let page = wikipedia.page_from_pageid("4138548".to_owned());
-        let html = page.get_html_content().unwrap();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}, {\"ns\": 0}, {\"title\": \"World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("not json".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                html,
-                "hello".to_owned()
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "revisions".to_owned()),
-                    ("rvprop".to_owned(), "content".to_owned()),
-                    ("rvlimit".to_owned(), "1".to_owned()),
-                    ("rvparse".to_owned(), "".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
-                    ("format".to_owned(), "json".to_owned()),
-                    ("action".to_owned(), "query".to_owned()),
-                    ("pageids".to_owned(), "4138548".to_owned()),
-                    ]]);
+                page.get_links().unwrap().map(|l| l.title).collect::<Vec<_>>(),
+                vec!["Hello".to_owned(), "World".to_owned()]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 2);
     }
 
     #[test]
-    fn page_summary() {
+    fn get_links_try_iter() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
-        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
-        let summary = page.get_summary().unwrap();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("not json".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        let mut links = page.get_links().unwrap().try_iter();
+        assert_eq!(links.next().unwrap().unwrap(), iter::Link { title: "Hello".to_owned() });
+        assert!(links.next().unwrap().is_err());
+        assert_eq!(links.next().unwrap().unwrap(), iter::Link { title: "World".to_owned() });
+        assert!(links.next().is_none());
+        let arguments = wikipedia.client.arguments.lock().unwrap();
+        assert_eq!(arguments.len(), 3);
+        assert_eq!(arguments[1], arguments[2]);
+    }
+
+    #[test]
+    fn get_templates() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"templates\":[{\"ns\": 10, \"title\": \"Template:Stub\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"templates\":[{\"ns\": 10, \"title\": \"Template:Cleanup\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                summary,
-                "hello".to_owned()
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+                page.get_templates().unwrap().collect::<Vec<_>>(),
+                vec![
+                iter::Template {
+                    title: "Template:Stub".to_owned(),
+                },
+                iter::Template {
+                    title: "Template:Cleanup".to_owned(),
+                }
+                ]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "extracts".to_owned()),
-                    ("explaintext".to_owned(), "".to_owned()),
-                    ("exintro".to_owned(), "".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
+                vec![
+                vec![
+                    ("prop".to_owned(), "templates".to_owned()),
+                    ("tllimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned())
-                    ]]);
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ],
+                vec![
+                    ("prop".to_owned(), "templates".to_owned()),
+                    ("tllimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("lol".to_owned(), "1".to_owned()),
+                ]
+                ]);
     }
 
     #[test]
-    fn page_redirect_summary() {
+    fn get_iwlinks() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"to\":\"hello world\"}]}}".to_owned());
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extract\":\"hello\"}}}}".to_owned());
-        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
-        let summary = page.get_summary().unwrap();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"iwcontinue\":\"1|wikt|a\"},\"query\":{\"pages\":{\"a\":{\"iwlinks\":[{\"prefix\": \"wikt\", \"*\": \"world\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"iwlinks\":[{\"prefix\": \"q\", \"*\": \"World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                summary,
-                "hello".to_owned()
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                page.get_iwlinks().unwrap().collect::<Vec<_>>(),
                 vec![
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                "https://en.wikipedia.org/w/api.php".to_owned(),
+                iter::IwLink {
+                    prefix: "wikt".to_owned(),
+                    title: "world".to_owned(),
+                },
+                iter::IwLink {
+                    prefix: "q".to_owned(),
+                    title: "World".to_owned(),
+                }
                 ]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
                 vec![
                 vec![
-                    ("prop".to_owned(), "extracts".to_owned()),
-                    ("explaintext".to_owned(), "".to_owned()),
-                    ("exintro".to_owned(), "".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
+                    ("prop".to_owned(), "iwlinks".to_owned()),
+                    ("iwlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned())
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
                 ],
                 vec![
-                    ("prop".to_owned(), "extracts".to_owned()),
-                    ("explaintext".to_owned(), "".to_owned()),
-                    ("exintro".to_owned(), "".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
+                    ("prop".to_owned(), "iwlinks".to_owned()),
+                    ("iwlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "hello world".to_owned())
-                    ]
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("iwcontinue".to_owned(), "1|wikt|a".to_owned()),
                 ]
-                );
+                ]);
     }
 
     #[test]
-    fn page_images() {
+    fn get_backlinks() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"title\":\"Image 1\", \"imageinfo\":[{\"url\": \"http://example.com/image1.jpg\", \"descriptionurl\": \"http://example.com/image1.jpg.html\"}]}}}}".to_owned());
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"title\":\"Image 2\", \"imageinfo\":[{\"url\": \"http://example.com/image2.jpg\", \"descriptionurl\": \"http://example.com/image2.jpg.html\"}]}}}}".to_owned());
-        let page = wikipedia.page_from_title("Parkinson's law of triviality".to_owned());
-        let images = page.get_images().unwrap().collect::<Vec<_>>();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"blcontinue\":\"0|2\"},\"query\":{\"backlinks\":[{\"pageid\": 1, \"ns\": 0, \"title\": \"Hello\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"backlinks\":[{\"pageid\": 2, \"ns\": 0, \"title\": \"World\"}]}}".to_owned());
+        let page = wikipedia.page_from_pageid("123".to_owned());
         assert_eq!(
-                images,
+                page.get_backlinks().unwrap().collect::<Vec<_>>(),
                 vec![
-                iter::Image {
-                    url: "http://example.com/image1.jpg".to_owned(),
-                    title: "Image 1".to_owned(),
-                    description_url: "http://example.com/image1.jpg.html".to_owned(),
+                iter::Backlink {
+                    title: "Hello".to_owned(),
+                    pageid: 1,
                 },
-                iter::Image {
-                    url: "http://example.com/image2.jpg".to_owned(),
-                    title: "Image 2".to_owned(),
-                    description_url: "http://example.com/image2.jpg.html".to_owned(),
+                iter::Backlink {
+                    title: "World".to_owned(),
+                    pageid: 2,
                 }
                 ]);
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
                 vec![
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                "https://en.wikipedia.org/w/api.php".to_owned(),
+                vec![
+                    ("list".to_owned(), "backlinks".to_owned()),
+                    ("blpageid".to_owned(), "123".to_owned()),
+                    ("bllimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ],
+                vec![
+                    ("list".to_owned(), "backlinks".to_owned()),
+                    ("blpageid".to_owned(), "123".to_owned()),
+                    ("bllimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("blcontinue".to_owned(), "0|2".to_owned()),
+                ]
                 ]);
+    }
+
+    #[test]
+    fn query_prop_iter() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"ticontinue\":\"2\"},\"query\":{\"pages\":{\"a\":{\"transcludedin\":[{\"pageid\": 1, \"title\": \"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"transcludedin\":[{\"pageid\": 2, \"title\": \"World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("Template:Hello".to_owned());
+        let titles = page.query_prop_iter("transcludedin", "tilimit", "transcludedin").unwrap()
+            .map(|x| x["title"].as_str().unwrap().to_owned())
+            .collect::<Vec<_>>();
+        assert_eq!(titles, vec!["Hello".to_owned(), "World".to_owned()]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
                 vec![
-                vec![
-                    ("generator".to_owned(), "images".to_owned()),
-                    ("gimlimit".to_owned(), "max".to_owned()),
-                    ("prop".to_owned(), "imageinfo".to_owned()),
-                    ("iiprop".to_owned(), "url".to_owned()),
+                vec![
+                    ("prop".to_owned(), "transcludedin".to_owned()),
+                    ("tilimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned()),
-                    ("continue".to_owned(), "".to_owned())
+                    ("titles".to_owned(), "Template:Hello".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
                 ],
                 vec![
-                    ("generator".to_owned(), "images".to_owned()),
-                    ("gimlimit".to_owned(), "max".to_owned()),
-                    ("prop".to_owned(), "imageinfo".to_owned()),
-                    ("iiprop".to_owned(), "url".to_owned()),
+                    ("prop".to_owned(), "transcludedin".to_owned()),
+                    ("tilimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "Parkinson\'s law of triviality".to_owned()),
-                    ("lol".to_owned(), "1".to_owned())
-                ]
+                    ("titles".to_owned(), "Template:Hello".to_owned()),
+                    ("ticontinue".to_owned(), "2".to_owned()),
                 ]
-                );
+                ]);
     }
 
     #[test]
-    fn page_coordinates() {
+    fn get_revisions() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"coordinates\":[{\"lat\": 2.1, \"lon\":-1.3}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"rvcontinue\":\"20180101|1\"},\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"revid\":3,\"parentid\":2,\"user\":\"Hello\",\"timestamp\":\"2018-03-04T10:20:00Z\",\"size\":100,\"comment\":\"typo\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"revisions\":[{\"revid\":2,\"parentid\":0,\"userhidden\":\"\",\"timestamp\":\"2018-01-01T00:00:00Z\",\"size\":90,\"commenthidden\":\"\"}]}}}}".to_owned());
         let page = wikipedia.page_from_title("World".to_owned());
-        let coordinates = page.get_coordinates().unwrap().unwrap();
         assert_eq!(
-                coordinates,
-                (2.1, -1.3)
-                );
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
+                page.get_revisions().unwrap().collect::<Vec<_>>(),
+                vec![
+                iter::Revision {
+                    revid: 3,
+                    parentid: 2,
+                    timestamp: "2018-03-04T10:20:00Z".to_owned(),
+                    user: Some("Hello".to_owned()),
+                    comment: Some("typo".to_owned()),
+                    size: 100,
+                },
+                iter::Revision {
+                    revid: 2,
+                    parentid: 0,
+                    timestamp: "2018-01-01T00:00:00Z".to_owned(),
+                    user: None,
+                    comment: None,
+                    size: 90,
+                }
+                ]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "coordinates".to_owned()),
-                    ("colimit".to_owned(), "max".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
+                vec![
+                vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "ids|timestamp|user|comment|size".to_owned()),
+                    ("rvlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "World".to_owned())
-                    ]]);
-    }
-
-    #[test]
-    fn page_no_coordinates() {
-        let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{}}}}".to_owned());
-        let page = wikipedia.page_from_title("World".to_owned());
-        assert!(page.get_coordinates().unwrap().is_none());
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
-        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "coordinates".to_owned()),
-                    ("colimit".to_owned(), "max".to_owned()),
-                    ("redirects".to_owned(), "".to_owned()),
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ],
+                vec![
+                    ("prop".to_owned(), "revisions".to_owned()),
+                    ("rvprop".to_owned(), "ids|timestamp|user|comment|size".to_owned()),
+                    ("rvlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "World".to_owned())
-                    ]]);
+                    ("titles".to_owned(), "World".to_owned()),
+                    ("rvcontinue".to_owned(), "20180101|1".to_owned()),
+                ]
+                ]);
     }
 
     #[test]
-    fn get_references() {
+    fn get_redirects() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"extlinks\":[{\"*\": \"//example.com/reference1.html\"}]}}}}".to_owned());
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"extlinks\":[{\"*\": \"//example.com/reference2.html\"}]}}}}".to_owned());
-        let page = wikipedia.page_from_title("World".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"rdcontinue\":\"3\"},\"query\":{\"pages\":{\"a\":{\"redirects\":[{\"pageid\":2,\"ns\":0,\"title\":\"Bikeshedding\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"redirects\":[{\"pageid\":3,\"ns\":0,\"title\":\"Parkinson's law of triviality\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("Law of triviality".to_owned());
         assert_eq!(
-                page.get_references().unwrap().collect::<Vec<_>>(),
+                page.get_redirects().unwrap().collect::<Vec<_>>(),
                 vec![
-                iter::Reference {
-                    url: "http://example.com/reference1.html".to_owned(),
+                iter::Redirect {
+                    title: "Bikeshedding".to_owned(),
+                    pageid: 2,
                 },
-                iter::Reference {
-                    url: "http://example.com/reference2.html".to_owned(),
+                iter::Redirect {
+                    title: "Parkinson's law of triviality".to_owned(),
+                    pageid: 3,
                 }
                 ]);
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec![
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                ]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
-                vec![vec![
-                    ("prop".to_owned(), "extlinks".to_owned()),
-                    ("ellimit".to_owned(), "max".to_owned()),
+                vec![
+                vec![
+                    ("prop".to_owned(), "redirects".to_owned()),
+                    ("rdlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "World".to_owned()),
-                    ("continue".to_owned(), "".to_owned())
+                    ("titles".to_owned(), "Law of triviality".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
                 ],
                 vec![
-                    ("prop".to_owned(), "extlinks".to_owned()),
-                    ("ellimit".to_owned(), "max".to_owned()),
+                    ("prop".to_owned(), "redirects".to_owned()),
+                    ("rdlimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
-                    ("titles".to_owned(), "World".to_owned()),
-                    ("lol".to_owned(), "1".to_owned())
+                    ("titles".to_owned(), "Law of triviality".to_owned()),
+                    ("rdcontinue".to_owned(), "3".to_owned()),
                 ]
                 ]);
     }
 
     #[test]
-    fn get_links() {
+    fn get_contributors() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"Hello\"}]}}}}".to_owned());
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"links\":[{\"title\": \"World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"pccontinue\":\"1|2\"},\"query\":{\"pages\":{\"a\":{\"contributors\":[{\"userid\":1,\"name\":\"Hello\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"contributors\":[{\"userid\":2,\"name\":\"World\"}]}}}}".to_owned());
         let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
-                page.get_links().unwrap().collect::<Vec<_>>(),
+                page.get_contributors().unwrap().collect::<Vec<_>>(),
                 vec![
-                iter::Link {
-                    title: "Hello".to_owned(),
+                iter::Contributor {
+                    name: "Hello".to_owned(),
+                    userid: 1,
                 },
-                iter::Link {
-                    title: "World".to_owned(),
+                iter::Contributor {
+                    name: "World".to_owned(),
+                    userid: 2,
                 }
                 ]);
-        assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec![
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                ]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
                 vec![
                 vec![
-                    ("prop".to_owned(), "links".to_owned()),
-                    ("plnamespace".to_owned(), "0".to_owned()),
-                    ("ellimit".to_owned(), "max".to_owned()),
+                    ("prop".to_owned(), "contributors".to_owned()),
+                    ("pclimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
                     ("titles".to_owned(), "World".to_owned()),
                     ("continue".to_owned(), "".to_owned()),
                 ],
                 vec![
-                    ("prop".to_owned(), "links".to_owned()),
-                    ("plnamespace".to_owned(), "0".to_owned()),
-                    ("ellimit".to_owned(), "max".to_owned()),
+                    ("prop".to_owned(), "contributors".to_owned()),
+                    ("pclimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
                     ("titles".to_owned(), "World".to_owned()),
-                    ("lol".to_owned(), "1".to_owned()),
+                    ("pccontinue".to_owned(), "1|2".to_owned()),
                 ]
                 ]);
     }
@@ -1207,28 +5119,43 @@ mod test {
     #[test]
     fn get_categories() {
         let wikipedia = Wikipedia::<MockClient>::default();
-        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"categories\":[{\"title\": \"Hello\"}]}}}}".to_owned());
-        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"categories\":[{\"title\": \"Category: World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"0\":{\"id\":0,\"*\":\"\"},\"14\":{\"id\":14,\"*\":\"Category\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"lol\":\"1\"},\"query\":{\"pages\":{\"a\":{\"categories\":[{\"ns\": 14, \"title\": \"Category:Hello\", \"hidden\": \"\", \"sortkey\": \"48454c4c4f\", \"sortkeyprefix\": \"\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"categories\":[{\"ns\": 14, \"title\": \"Category:World\"}]}}}}".to_owned());
         let page = wikipedia.page_from_title("World".to_owned());
         assert_eq!(
                 page.get_categories().unwrap().collect::<Vec<_>>(),
                 vec![
                 iter::Category {
                     title: "Hello".to_owned(),
+                    pageid: None,
+                    ns: 14,
+                    hidden: true,
+                    sortkey: Some("48454c4c4f".to_owned()),
+                    sortkeyprefix: Some("".to_owned()),
                 },
                 iter::Category {
                     title: "World".to_owned(),
+                    pageid: None,
+                    ns: 14,
+                    hidden: false,
+                    sortkey: None,
+                    sortkeyprefix: None,
                 }
                 ]);
         assert_eq!(*wikipedia.client.url.lock().unwrap(),
-                vec![
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                "https://en.wikipedia.org/w/api.php".to_owned(),
-                ]);
+                vec!["https://en.wikipedia.org/w/api.php".to_owned(); 3]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
                 vec![
+                vec![
+                    ("meta".to_owned(), "siteinfo".to_owned()),
+                    ("siprop".to_owned(), "namespaces".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                ],
                 vec![
                     ("prop".to_owned(), "categories".to_owned()),
+                    ("clprop".to_owned(), "hidden|sortkey".to_owned()),
                     ("cllimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
@@ -1237,6 +5164,7 @@ mod test {
                 ],
                 vec![
                     ("prop".to_owned(), "categories".to_owned()),
+                    ("clprop".to_owned(), "hidden|sortkey".to_owned()),
                     ("cllimit".to_owned(), "max".to_owned()),
                     ("format".to_owned(), "json".to_owned()),
                     ("action".to_owned(), "query".to_owned()),
@@ -1246,6 +5174,175 @@ mod test {
                 ]);
     }
 
+    #[test]
+    fn get_categories_with_ids() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"0\":{\"id\":0,\"*\":\"\"},\"14\":{\"id\":14,\"*\":\"Category\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"categories\":[{\"ns\": 14, \"title\": \"Category:Hello\"}, {\"ns\": 14, \"title\": \"Category:World\"}]}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"10\":{\"pageid\": 10, \"ns\": 14, \"title\": \"Category:Hello\"}, \"-1\":{\"ns\": 14, \"title\": \"Category:World\", \"missing\": \"\"}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_categories_with_ids().unwrap().into_iter().map(|c| (c.title, c.pageid)).collect::<Vec<_>>(),
+                vec![
+                ("Hello".to_owned(), Some(10)),
+                ("World".to_owned(), None),
+                ]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[2],
+                vec![
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("titles".to_owned(), "Category:Hello|Category:World".to_owned()),
+                ]);
+    }
+
+    #[test]
+    fn get_categories_localized() {
+        let wikipedia = Wikipedia::<MockClient>::default().with_language("es");
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"14\":{\"id\":14,\"*\":\"Categoría\",\"canonical\":\"Category\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"categories\":[{\"ns\": 14, \"title\": \"Categoría:Lenguajes de programación\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("Lisp".to_owned());
+        assert_eq!(
+                page.get_categories().unwrap().map(|c| c.title).collect::<Vec<_>>(),
+                vec!["Lenguajes de programación".to_owned()]);
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://es.wikipedia.org/w/api.php".to_owned(),
+                "https://es.wikipedia.org/w/api.php".to_owned(),
+                ]);
+    }
+
+    #[test]
+    fn get_visible_categories() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"0\":{\"id\":0,\"*\":\"\"},\"14\":{\"id\":14,\"*\":\"Category\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"a\":{\"categories\":[{\"title\": \"Category:Hello\", \"hidden\": \"\"}, {\"title\": \"Category:World\"}]}}}}".to_owned());
+        let page = wikipedia.page_from_title("World".to_owned());
+        assert_eq!(
+                page.get_visible_categories().unwrap().map(|c| c.title).collect::<Vec<_>>(),
+                vec!["World".to_owned()]);
+    }
+
+    #[test]
+    fn category_members() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"continue\": {\"cmcontinue\":\"page|1\",\"continue\":\"-||\"},\"query\":{\"categorymembers\":[{\"pageid\": 1, \"ns\": 0, \"title\": \"Rust\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"categorymembers\":[{\"pageid\": 2, \"ns\": 14, \"title\": \"Category:Lisp\"}]}}".to_owned());
+        assert_eq!(
+                wikipedia.category_members("Category:Programming languages").unwrap().collect::<Vec<_>>(),
+                vec![
+                iter::CategoryMember {
+                    title: "Rust".to_owned(),
+                    pageid: 1,
+                    ns: 0,
+                },
+                iter::CategoryMember {
+                    title: "Category:Lisp".to_owned(),
+                    pageid: 2,
+                    ns: 14,
+                }
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("list".to_owned(), "categorymembers".to_owned()),
+                    ("cmtitle".to_owned(), "Category:Programming languages".to_owned()),
+                    ("cmlimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("continue".to_owned(), "".to_owned()),
+                ],
+                vec![
+                    ("list".to_owned(), "categorymembers".to_owned()),
+                    ("cmtitle".to_owned(), "Category:Programming languages".to_owned()),
+                    ("cmlimit".to_owned(), "max".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "query".to_owned()),
+                    ("cmcontinue".to_owned(), "page|1".to_owned()),
+                    ("continue".to_owned(), "-||".to_owned()),
+                ]
+                ]);
+    }
+
+    #[test]
+    fn format_days() {
+        assert_eq!(super::format_days(0), "19700101".to_owned());
+        assert_eq!(super::format_days(11016), "20000229".to_owned());
+        assert_eq!(super::format_days(17595), "20180305".to_owned());
+    }
+
+    #[test]
+    fn page_pageviews() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"law of triviality\",\"to\":\"Law of triviality\"}],\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"items\":[{\"timestamp\":\"2018030400\",\"views\":10},{\"timestamp\":\"2018030500\",\"views\":12}]}".to_owned());
+        let page = wikipedia.page_from_title("law of triviality".to_owned());
+        assert_eq!(
+                page.request_pageviews("20180304", "20180305").unwrap(),
+                vec![("20180304".to_owned(), 10), ("20180305".to_owned(), 12)]);
+        assert_eq!(wikipedia.client.url.lock().unwrap()[1],
+                "https://wikimedia.org/api/rest_v1/metrics/pageviews/per-article/en.wikipedia.org/all-access/user/Law_of_triviality/daily/2018030400/2018030500".to_owned());
+        assert_eq!(wikipedia.client.arguments.lock().unwrap()[1], vec![]);
+    }
+
+    #[test]
+    fn page_pageviews_redirect() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"redirects\":[{\"from\":\"Bikeshedding\",\"to\":\"Law of triviality\"}],\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"items\":[]}".to_owned());
+        let page = wikipedia.page_from_title("Bikeshedding".to_owned());
+        assert_eq!(page.request_pageviews("20180304", "20180305").unwrap(), vec![]);
+        assert_eq!(wikipedia.client.url.lock().unwrap()[2],
+                "https://wikimedia.org/api/rest_v1/metrics/pageviews/per-article/en.wikipedia.org/all-access/user/Law_of_triviality/daily/2018030400/2018030500".to_owned());
+    }
+
+    #[test]
+    fn page_pageviews_no_days() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let page = wikipedia.page_from_title("Law of triviality".to_owned());
+        assert_eq!(page.get_pageviews(0).unwrap(), vec![]);
+        assert!(wikipedia.client.url.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn page_rest_summary() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        let info = "{\"query\":{\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}";
+        wikipedia.client.response.lock().unwrap().push(info.to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"title\":\"Law_of_triviality\",\"titles\":{\"normalized\":\"Law of triviality\"},\"description\":\"Observation by Parkinson\",\"extract\":\"Hello\",\"thumbnail\":{\"source\":\"https://upload.wikimedia.org/a.png\"},\"content_urls\":{\"desktop\":{\"page\":\"https://en.wikipedia.org/wiki/Law_of_triviality\"},\"mobile\":{\"page\":\"https://en.m.wikipedia.org/wiki/Law_of_triviality\"}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push(info.to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"title\":\"Law_of_triviality\",\"extract\":\"Hola\"}".to_owned());
+        let summary = wikipedia.page_from_title("Law of triviality".to_owned()).get_rest_summary().unwrap();
+        assert_eq!(summary.title, "Law of triviality".to_owned());
+        assert_eq!(summary.description, Some("Observation by Parkinson".to_owned()));
+        assert_eq!(summary.extract, "Hello".to_owned());
+        assert_eq!(summary.thumbnail_url, Some("https://upload.wikimedia.org/a.png".to_owned()));
+        assert_eq!(summary.content_urls["mobile"], "https://en.m.wikipedia.org/wiki/Law_of_triviality".to_owned());
+        wikipedia.rest_url = Some("https://wiki.example.com/{language}/rest/".to_owned());
+        let summary = wikipedia.page_from_title("Law of triviality".to_owned()).get_rest_summary().unwrap();
+        assert_eq!(summary.title, "Law of triviality".to_owned());
+        assert_eq!(summary.description, None);
+        assert!(summary.content_urls.is_empty());
+        assert_eq!(*wikipedia.client.url.lock().unwrap(), vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://en.wikipedia.org/api/rest_v1/page/summary/Law_of_triviality".to_owned(),
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://wiki.example.com/en/rest/page/summary/Law_of_triviality".to_owned(),
+        ]);
+    }
+
+    #[test]
+    fn page_rest_summary_redirect() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"normalized\":[{\"from\":\"bikeshedding\",\"to\":\"Bikeshedding\"}],\"redirects\":[{\"from\":\"Bikeshedding\",\"to\":\"Law of triviality\"}],\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"pages\":{\"1\":{\"pageid\":1,\"title\":\"Law of triviality\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"title\":\"Law_of_triviality\",\"extract\":\"Hello\"}".to_owned());
+        let summary = wikipedia.page_from_title("bikeshedding".to_owned()).get_rest_summary().unwrap();
+        assert_eq!(summary.title, "Law of triviality".to_owned());
+        assert_eq!(wikipedia.client.url.lock().unwrap()[2],
+                "https://en.wikipedia.org/api/rest_v1/page/summary/Law_of_triviality".to_owned());
+    }
+
     #[test]
     fn sections() {
         let wikipedia = Wikipedia::<MockClient>::default();
@@ -1266,6 +5363,151 @@ mod test {
                     ]]);
     }
 
+    #[test]
+    fn sections_detailed() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"parse\":{\"sections\":[{\"toclevel\":1,\"level\":\"2\",\"line\":\"hello\",\"number\":\"1\",\"index\":\"1\",\"anchor\":\"hello\"},{\"toclevel\":2,\"level\":\"3\",\"line\":\"hello world\",\"number\":\"1.1\",\"index\":\"2\",\"anchor\":\"hello_world\"}]}}".to_owned());
+        let page = wikipedia.page_from_pageid("123".to_owned());
+        assert_eq!(
+                page.get_sections_detailed().unwrap(),
+                vec![
+                Section {
+                    line: "hello".to_owned(),
+                    level: 2,
+                    number: "1".to_owned(),
+                    anchor: "hello".to_owned(),
+                    index: "1".to_owned(),
+                },
+                Section {
+                    line: "hello world".to_owned(),
+                    level: 3,
+                    number: "1.1".to_owned(),
+                    anchor: "hello_world".to_owned(),
+                    index: "2".to_owned(),
+                },
+                ]);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![vec![
+                    ("prop".to_owned(), "sections".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "parse".to_owned()),
+                    ("pageid".to_owned(), "123".to_owned())
+                    ]]);
+    }
+
+    #[test]
+    fn section_content() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let content = "Intro\n\n== Examples ==\nFirst\n\n=== Nested ===\nSecond\n\n== a == b ==\nThird\n\n== Last ==\nFourth\n";
+        for _ in 0..4 {
+            wikipedia.client.response.lock().unwrap().push(format!("{{\"query\":{{\"pages\":{{\"a\":{{\"extract\":{:?}}}}}}}}}", content));
+        }
+        let page = wikipedia.page_from_pageid("123".to_owned());
+        assert_eq!(
+                page.get_section_content("Examples").unwrap(),
+                Some("First\n\n=== Nested ===\nSecond\n\n".to_owned()));
+        assert_eq!(
+                page.get_section_content("a == b").unwrap(),
+                Some("Third\n\n".to_owned()));
+        assert_eq!(
+                page.get_section_content("Last").unwrap(),
+                Some("Fourth\n".to_owned()));
+        assert_eq!(page.get_section_content("Missing").unwrap(), None);
+    }
+
+    #[test]
+    fn all_sections() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        let content = "Intro\n\n== Examples ==\nFirst\n\n=== Nested ===\nSecond\n\n== a == b ==\nThird\n\n== Last ==\nFourth\n";
+        wikipedia.client.response.lock().unwrap().push(format!("{{\"query\":{{\"pages\":{{\"a\":{{\"extract\":{:?}}}}}}}}}", content));
+        let page = wikipedia.page_from_pageid("123".to_owned());
+        assert_eq!(
+                page.get_all_sections().unwrap(),
+                vec![
+                ("Examples".to_owned(), "First\n\n=== Nested ===\nSecond\n\n".to_owned()),
+                ("Nested".to_owned(), "Second\n\n".to_owned()),
+                ("a == b".to_owned(), "Third\n\n".to_owned()),
+                ("Last".to_owned(), "Fourth\n".to_owned()),
+                ]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn section_content_by_index() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"parse\":{\"wikitext\":{\"*\":\"== hello ==\\nworld\"}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"error\":{\"code\":\"nosuchsection\",\"info\":\"There is no section 9.\"}}".to_owned());
+        let page = wikipedia.page_from_pageid("123".to_owned());
+        assert_eq!(
+                page.get_section_content_by_index(1, false).unwrap(),
+                Some("== hello ==\nworld".to_owned()));
+        assert_eq!(page.get_section_content_by_index(9, true).unwrap(), None);
+        assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
+                vec![
+                vec![
+                    ("prop".to_owned(), "wikitext".to_owned()),
+                    ("section".to_owned(), "1".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "parse".to_owned()),
+                    ("pageid".to_owned(), "123".to_owned())
+                ],
+                vec![
+                    ("prop".to_owned(), "text".to_owned()),
+                    ("section".to_owned(), "9".to_owned()),
+                    ("format".to_owned(), "json".to_owned()),
+                    ("action".to_owned(), "parse".to_owned()),
+                    ("pageid".to_owned(), "123".to_owned())
+                ]
+                ]);
+    }
+
+    #[test]
+    fn set_language() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"languages\":[{\"*\":\"English\", \"code\":\"en\"}, {\"*\":\"español\", \"code\":\"es\"}]}}".to_owned());
+        wikipedia.set_language("es").unwrap();
+        assert_eq!(wikipedia.language, "es".to_owned());
+        match wikipedia.set_language("english") {
+            Err(super::Error::InvalidParameter(p)) => assert_eq!(p, "language".to_owned()),
+            r => panic!("unexpected result {:?}", r),
+        }
+        assert_eq!(wikipedia.language, "es".to_owned());
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn refresh_languages() {
+        let wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"languages\":[{\"*\":\"hello\", \"code\":\"world\"}]}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"languages\":[{\"*\":\"foo\", \"code\":\"bar\"}]}}".to_owned());
+        assert_eq!(wikipedia.get_languages().unwrap(), vec![("world".to_owned(), "hello".to_owned())]);
+        assert_eq!(wikipedia.refresh_languages().unwrap(), vec![("bar".to_owned(), "foo".to_owned())]);
+        assert_eq!(wikipedia.get_languages().unwrap(), vec![("bar".to_owned(), "foo".to_owned())]);
+        assert_eq!(wikipedia.client.arguments.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn for_site() {
+        let wikipedia = Wikipedia::<MockClient>::for_site("https://wiki.example.com/w/api.php");
+        assert_eq!(wikipedia.base_url(), "https://wiki.example.com/w/api.php".to_owned());
+    }
+
+    #[test]
+    fn namespaces() {
+        let mut wikipedia = Wikipedia::<MockClient>::default();
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"0\":{\"id\":0,\"*\":\"\"},\"14\":{\"id\":14,\"*\":\"Category\"}}}}".to_owned());
+        wikipedia.client.response.lock().unwrap().push("{\"query\":{\"namespaces\":{\"14\":{\"id\":14,\"*\":\"Categoría\"}}}}".to_owned());
+        assert_eq!(wikipedia.get_namespaces().unwrap()[&14], "Category".to_owned());
+        assert_eq!(wikipedia.get_namespaces().unwrap()[&0], "".to_owned());
+        wikipedia.language = "es".to_owned();
+        assert_eq!(wikipedia.get_namespaces().unwrap()[&14], "Categoría".to_owned());
+        assert_eq!(*wikipedia.client.url.lock().unwrap(),
+                vec![
+                "https://en.wikipedia.org/w/api.php".to_owned(),
+                "https://es.wikipedia.org/w/api.php".to_owned(),
+                ]);
+    }
+
     #[test]
     fn languages() {
         let wikipedia = Wikipedia::<MockClient>::default();
@@ -1277,6 +5519,7 @@ mod test {
                     ("bar".to_owned(), "foo".to_owned()),
                 ]
                 );
+        assert_eq!(wikipedia.get_languages().unwrap().len(), 2);
         assert_eq!(*wikipedia.client.url.lock().unwrap(),
                 vec!["https://en.wikipedia.org/w/api.php".to_owned()]);
         assert_eq!(*wikipedia.client.arguments.lock().unwrap(),
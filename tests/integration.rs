@@ -65,6 +65,31 @@ mod tests {
         assert_eq!(page.get_pageid().unwrap(), "4138548".to_owned());
     }
 
+    #[test]
+    fn disambiguation() {
+        let wikipedia = w();
+        let page = wikipedia.page_from_title("Mercury".to_owned());
+        assert!(page.is_disambiguation().unwrap());
+        let page = wikipedia.page_from_title("Bikeshedding".to_owned());
+        assert!(!page.is_disambiguation().unwrap());
+    }
+
+    #[test]
+    fn url() {
+        let wikipedia = w();
+        let page = wikipedia.page_from_title("Bikeshedding".to_owned());
+        assert_eq!(page.get_url().unwrap(), "https://en.wikipedia.org/wiki/Law_of_triviality".to_owned());
+    }
+
+    #[test]
+    fn in_language() {
+        let wikipedia = w();
+        let page = wikipedia.page_from_title("Law of triviality".to_owned());
+        let page = page.in_language("fr").unwrap().unwrap();
+        assert_eq!(page.get_title().unwrap(), "Loi de futilité de Parkinson".to_owned());
+        assert!(page.get_summary().unwrap().contains("Parkinson"));
+    }
+
     #[test]
     fn page_html_content() {
         let wikipedia = w();
@@ -212,6 +237,15 @@ mod tests {
         assert_eq!(set.len(), 7);
     }
 
+    #[test]
+    fn category_members() {
+        let mut wikipedia = w();
+        wikipedia.categories_results = "5".to_owned();
+        let members = wikipedia.category_members("Category:Programming languages").unwrap();
+        let set = members.take(11).map(|m| m.title).collect::<HashSet<_>>();
+        assert_eq!(set.len(), 11);
+    }
+
     #[test]
     fn sections() {
         let wikipedia = w();